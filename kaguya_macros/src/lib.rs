@@ -0,0 +1,242 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Procedural macros of KaguyaRs, re-exported by `kaguya_rs`.
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Span, Spacing, TokenStream, TokenTree};
+
+/// Generate a curried version `{name}_curried` of a non-generic function,
+/// the original function is kept untouched.
+///
+/// For `fn add(a: A, b: B, c: C) -> R`, the generated function is
+/// `fn add_curried(a: A) -> impl Fn(B) -> Box<dyn Fn(C) -> R>`,
+/// so it can be invoked as `add_curried(a)(b)(c)`.
+///
+/// As the returned closures can be invoked many times, all parameters except the last one
+/// must impl [`Clone`], and all parameter types must be `'static`.
+///
+/// ```
+/// extern crate kaguya_macros;
+/// use kaguya_macros::curried;
+///
+/// #[curried]
+/// fn add(a: i32, b: i32) -> i32 { a + b }
+///
+/// fn main() {
+///     assert_eq!(add_curried(1)(2), 3);
+/// }
+/// ```
+///
+/// Generic fn, including `where` clause, is rejected with a compile error:
+///
+/// ```compile_fail
+/// extern crate kaguya_macros;
+/// use kaguya_macros::curried;
+///
+/// #[curried]
+/// fn add<T: std::ops::Add<Output=T>>(a: T, b: T) -> T { a + b }
+///
+/// fn main() {}
+/// ```
+///
+/// ```compile_fail
+/// extern crate kaguya_macros;
+/// use kaguya_macros::curried;
+///
+/// #[curried]
+/// fn first(a: Vec<u8>, b: u8) -> Option<u8> where u8: Copy { a.first().cloned().or(Some(b)) }
+///
+/// fn main() {}
+/// ```
+#[proc_macro_attribute]
+pub fn curried(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return compile_error("#[curried] does not accept arguments");
+    }
+    let generated = match parse_fn(item.clone()).and_then(|sig| generate(&sig)) {
+        Ok(generated) => generated,
+        Err(msg) => return compile_error(msg),
+    };
+    let generated = match generated.parse::<TokenStream>() {
+        Ok(generated) => generated,
+        Err(_) => return compile_error("#[curried] failed to generate the curried fn of this signature"),
+    };
+    let mut ret = item;
+    ret.extend(generated);
+    ret
+}
+
+struct Signature {
+    vis: String,
+    name: String,
+    params: Vec<String>,
+    output: String,
+}
+
+fn compile_error(msg: &str) -> TokenStream {
+    vec![
+        TokenTree::Ident(Ident::new("compile_error", Span::call_site())),
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(Group::new(Delimiter::Parenthesis, TokenTree::Literal(Literal::string(msg)).into())),
+        TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+    ].into_iter().collect()
+}
+
+fn is_punct(token: &TokenTree, ch: char) -> bool {
+    match *token {
+        TokenTree::Punct(ref p) => p.as_char() == ch,
+        _ => false,
+    }
+}
+
+fn is_ident(token: &TokenTree, name: &str) -> bool {
+    match *token {
+        TokenTree::Ident(ref i) => i.to_string() == name,
+        _ => false,
+    }
+}
+
+fn is_single_colon(token: &TokenTree) -> bool {
+    match *token {
+        TokenTree::Punct(ref p) => p.as_char() == ':' && p.spacing() == Spacing::Alone,
+        _ => false,
+    }
+}
+
+fn is_joint_colon(token: &TokenTree) -> bool {
+    match *token {
+        TokenTree::Punct(ref p) => p.as_char() == ':' && p.spacing() == Spacing::Joint,
+        _ => false,
+    }
+}
+
+fn to_string(tokens: &[TokenTree]) -> String {
+    tokens.iter().cloned().collect::<TokenStream>().to_string()
+}
+
+fn parse_fn(item: TokenStream) -> Result<Signature, &'static str> {
+    let tokens: Vec<TokenTree> = item.into_iter().collect();
+    let fn_pos = tokens.iter().position(|t| is_ident(t, "fn"))
+        .ok_or("#[curried] can only be applied on fn")?;
+
+    // visibility, skipping outer attributes
+    let mut vis = Vec::new();
+    let mut i = 0;
+    while i < fn_pos {
+        if is_punct(&tokens[i], '#') {
+            i += 2;
+            continue;
+        }
+        if is_ident(&tokens[i], "const") || is_ident(&tokens[i], "unsafe")
+            || is_ident(&tokens[i], "async") || is_ident(&tokens[i], "extern") {
+            return Err("#[curried] only supports plain fn");
+        }
+        vis.push(tokens[i].clone());
+        i += 1;
+    }
+
+    let name = match tokens.get(fn_pos + 1) {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        _ => return Err("#[curried] expects a fn name"),
+    };
+    let params = match tokens.get(fn_pos + 2) {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => group.stream(),
+        _ => return Err("#[curried] does not support generic fn"),
+    };
+
+    // the last token must be the body, e.g. not a declaration without body
+    let body_pos = tokens.len() - 1;
+    match tokens[body_pos] {
+        TokenTree::Group(ref group) if group.delimiter() == Delimiter::Brace && body_pos > fn_pos + 2 => {}
+        _ => return Err("#[curried] expects a fn with body"),
+    }
+    let rest = &tokens[fn_pos + 3..body_pos];
+    if rest.iter().any(|t| is_ident(t, "where")) {
+        return Err("#[curried] does not support generic fn");
+    }
+    let output = if rest.len() >= 2 && is_punct(&rest[0], '-') && is_punct(&rest[1], '>') {
+        to_string(&rest[2..])
+    } else {
+        "()".to_string()
+    };
+
+    let params = split_params(params)?;
+    if params.is_empty() {
+        return Err("#[curried] expects at least one parameter");
+    }
+    Ok(Signature { vis: to_string(&vis), name, params, output })
+}
+
+/// Split parameters by top-level comma and return the type of each parameter
+fn split_params(params: TokenStream) -> Result<Vec<String>, &'static str> {
+    let tokens: Vec<TokenTree> = params.into_iter().collect();
+    let mut ret = Vec::new();
+    let mut start = 0;
+    let mut depth = 0;
+    for i in 0..=tokens.len() {
+        if i < tokens.len() {
+            let token = &tokens[i];
+            // `->` in fn pointer type is not a closing angle bracket
+            let is_arrow = i > 0 && is_punct(&tokens[i - 1], '-');
+            if is_punct(token, '<') {
+                depth += 1;
+            } else if is_punct(token, '>') && !is_arrow {
+                depth -= 1;
+            }
+            if !(depth == 0 && is_punct(token, ',')) {
+                continue;
+            }
+        }
+        let param = &tokens[start..i];
+        start = i + 1;
+        if param.is_empty() {
+            continue;
+        }
+        if param.iter().any(|t| is_ident(t, "self")) {
+            return Err("#[curried] does not support methods");
+        }
+        // first single colon, `::` is a path separator
+        let colon = (0..param.len()).find(|&j| {
+            is_single_colon(&param[j]) && !(j > 0 && is_joint_colon(&param[j - 1]))
+        }).ok_or("#[curried] expects typed parameters")?;
+        ret.push(to_string(&param[colon + 1..]));
+    }
+    Ok(ret)
+}
+
+fn generate(sig: &Signature) -> Result<String, &'static str> {
+    let n = sig.params.len();
+    let args = |k: usize| -> String {
+        (0..k).map(|i| format!("__arg{}.clone(), ", i)).collect()
+    };
+
+    // body of each layer, from the innermost one
+    let mut body = format!("{}({}__arg{})", sig.name, args(n - 1), n - 1);
+    let mut output = sig.output.clone();
+    for k in (1..n).rev() {
+        let clones: String = if k == n - 1 {
+            String::new()
+        } else {
+            (0..k).map(|i| format!("let __arg{0} = __arg{0}.clone(); ", i)).collect()
+        };
+        let closure = format!("move |__arg{}: {}| {{ {}{} }}", k, sig.params[k], clones, body);
+        if k == 1 {
+            body = closure;
+            output = format!("impl Fn({}) -> {}", sig.params[k], output);
+        } else {
+            output = format!("Box<dyn Fn({}) -> {}>", sig.params[k], output);
+            body = format!("Box::new({}) as {}", closure, output);
+        }
+    }
+
+    Ok(format!(
+        "#[doc = \"Curried version of `{name}`\"] {vis} fn {name}_curried(__arg0: {param}) -> {output} {{ {body} }}",
+        name = sig.name, vis = sig.vis, param = sig.params[0], output = output, body = body
+    ))
+}
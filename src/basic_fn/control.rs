@@ -0,0 +1,67 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Module control provide functions to control the flow of computation.
+
+use std::any::Any;
+use std::panic::{catch_unwind, UnwindSafe};
+
+/// Run a function and convert panic into [`Err`], so that a panicking stage will not break the pipeline
+///
+/// Note that the panic message is still printed by the panic hook.
+///
+/// # Arguments
+/// * `f`: f :: () -> T, must be [`UnwindSafe`], wrap captured references with
+///   [`AssertUnwindSafe`](std::panic::AssertUnwindSafe) if needed
+pub fn attempt<T>(f: impl FnOnce() -> T + UnwindSafe) -> Result<T, Box<dyn Any + Send>> {
+    catch_unwind(f)
+}
+
+/// Eliminate [`Option`], return `none_val` for [`None`] or `some_fn(x)` for `Some(x)`
+///
+/// # Arguments
+/// * `none_val`: R
+/// * `some_fn`: some_fn :: T -> R
+/// * `opt`: [`Option`] T
+pub fn option_fold<T,R>(none_val: R, some_fn: impl FnOnce(T) -> R, opt: Option<T>) -> R {
+    match opt {
+        None => none_val,
+        Some(x) => some_fn(x)
+    }
+}
+
+/// Eliminate [`Result`], return `err_fn(e)` for `Err(e)` or `ok_fn(x)` for `Ok(x)`
+///
+/// # Arguments
+/// * `err_fn`: err_fn :: E -> R
+/// * `ok_fn`: ok_fn :: T -> R
+/// * `res`: [`Result`] T E
+pub fn result_fold<T,E,R>(err_fn: impl FnOnce(E) -> R, ok_fn: impl FnOnce(T) -> R, res: Result<T, E>) -> R {
+    match res {
+        Err(e) => err_fn(e),
+        Ok(x) => ok_fn(x)
+    }
+}
+
+/// Fold the [`Ok`] values by combine while collecting every [`Err`], return `Ok` only if there is no error,
+/// unlike collecting into [`Result`] which stops at the first error
+///
+/// # Arguments
+/// * `init`: R, initial value of folding
+/// * `combine`: combine :: (R, T) -> R
+/// * `it`: [`IntoIterator`] [`Result`] T E
+pub fn fold_validate<T,E,R>(init: R, combine: impl Fn(R, T) -> R, it: impl IntoIterator<Item=Result<T, E>>) -> Result<R, Vec<E>> {
+    let mut errs = Vec::new();
+    let acc = it.into_iter().fold(init, |acc, res| match res {
+        Ok(x) => combine(acc, x),
+        Err(e) => {
+            errs.push(e);
+            acc
+        }
+    });
+    if errs.is_empty() { Ok(acc) } else { Err(errs) }
+}
\ No newline at end of file
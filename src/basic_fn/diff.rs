@@ -0,0 +1,101 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Module diff provide edit scripts between two sequences, based on the longest common subsequence.
+
+use std::error::Error;
+use std::fmt;
+
+use basic_fn::fun::lcs_table;
+
+/// One step of an edit script transforming a into b
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Diff<T> {
+    /// element in both a and b
+    Keep(T),
+    /// element only in b
+    Insert(T),
+    /// element only in a
+    Delete(T),
+}
+
+/// Error of [apply_diff](basic_fn::diff::apply_diff) when the script does not fit the source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchError {
+    /// index of source where the script stops fitting
+    pub index: usize,
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "diff does not match the source at index {}", self.index)
+    }
+}
+
+impl Error for PatchError {}
+
+/// Edit script transforming a into b, kept elements are the [lcs](basic_fn::fun::lcs) of a and b
+/// so the script is minimal. Around a change, deletions come before insertions
+///
+/// # Arguments
+/// * `a`: [`IntoIterator`] T, T must impl [`PartialEq`] and [`Clone`]
+/// * `b`: [`IntoIterator`] T
+pub fn diff<T: PartialEq + Clone>(a: impl IntoIterator<Item=T>, b: impl IntoIterator<Item=T>) -> Vec<Diff<T>> {
+    let a = a.into_iter().collect::<Vec<_>>();
+    let b = b.into_iter().collect::<Vec<_>>();
+    let table = lcs_table(&a, &b);
+    let (mut i, mut j) = (0, 0);
+    let mut ret = Vec::new();
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            ret.push(Diff::Keep(a[i].clone()));
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            ret.push(Diff::Delete(a[i].clone()));
+            i += 1;
+        } else {
+            ret.push(Diff::Insert(b[j].clone()));
+            j += 1;
+        }
+    }
+    ret.extend(a[i..].iter().cloned().map(Diff::Delete));
+    ret.extend(b[j..].iter().cloned().map(Diff::Insert));
+    ret
+}
+
+
+/// Apply edit script on a, return the patched sequence. Every `Keep` and `Delete` must match
+/// the element of a in order and a must be fully consumed, otherwise [`PatchError`] is returned
+///
+/// # Arguments
+/// * `diffs`: [`IntoIterator`] [`Diff`] T, T must impl [`PartialEq`]
+/// * `a`: [`IntoIterator`] T
+pub fn apply_diff<T: PartialEq>(diffs: impl IntoIterator<Item=Diff<T>>, a: impl IntoIterator<Item=T>) -> Result<Vec<T>, PatchError> {
+    let mut a = a.into_iter();
+    let mut index = 0;
+    let mut ret = Vec::new();
+    for d in diffs {
+        let (expected, keep) = match d {
+            Diff::Insert(x) => {
+                ret.push(x);
+                continue;
+            }
+            Diff::Keep(x) => (x, true),
+            Diff::Delete(x) => (x, false)
+        };
+        match a.next() {
+            Some(x) if x == expected => if keep { ret.push(x) },
+            _ => return Err(PatchError { index })
+        }
+        index += 1;
+    }
+    match a.next() {
+        Some(_) => Err(PatchError { index }),
+        None => Ok(ret)
+    }
+}
@@ -5,28 +5,35 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::iter::{Product, Sum, Zip};
+use std::iter::{from_fn, once, Fuse, Product, Sum, Zip};
 use std::ops::{Rem, Neg, Add, Sub, Div, Mul};
 use std::hash::Hash;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::cmp::Ordering;
+use std::cell::RefCell;
+use std::rc::Rc;
+use typeclass::monoid::Monoid;
 
 /// Used for data projection via mapping function.
 /// 
 /// # Arguments
 /// 
 /// * `f`: f :: T -> U
-/// * `it`: [`Iterator`] T
-pub fn map<T,U>(f: impl Fn(T) -> U, it: impl Iterator<Item=T>) -> impl Iterator<Item=U> {
-    it.map(f)
+/// * `it`: [`IntoIterator`] T
+pub fn map<T,U>(f: impl Fn(T) -> U, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=U> {
+    it.into_iter().map(f)
 }
 
 /// Used for sum [`Iterator`]<T>
 /// 
+/// T must impl [`Sum`], which is provided by std for all integer and float types, e.g. `i32`, `i64`, `f64`.
+/// Result is the same type as item, so iterator of references should be `cloned` first
+/// 
 /// # Arguments
-/// Mul
-/// * `it`: [`Iterator`] T
-pub fn sum<T: Sum>(it: impl Iterator<Item=T>) -> T {
-    it.sum()
+/// 
+/// * `it`: [`IntoIterator`] T
+pub fn sum<T: Sum>(it: impl IntoIterator<Item=T>) -> T {
+    it.into_iter().sum()
 }
 
 /// Used for fold the double end iterator from the beginning with init value and fold function
@@ -35,9 +42,9 @@ pub fn sum<T: Sum>(it: impl Iterator<Item=T>) -> T {
 /// 
 /// * `init`: initial point of folding, must be same type with final result
 /// * `f`: f :: (R, T) -> R, fold function
-/// * `it`: [`DoubleEndedIterator`] T
-pub fn foldl<T,R>(init: R, f: impl Fn(R,T) -> R, it: impl DoubleEndedIterator<Item=T>) -> R {
-    it.fold(init, f)
+/// * `it`: [`IntoIterator`] T, into [`DoubleEndedIterator`]
+pub fn foldl<T,R>(init: R, f: impl Fn(R,T) -> R, it: impl IntoIterator<Item=T, IntoIter=impl DoubleEndedIterator<Item=T>>) -> R {
+    it.into_iter().fold(init, f)
 }
 
 /// Used for fold the double end iterator from the end with init value and fold function
@@ -46,9 +53,9 @@ pub fn foldl<T,R>(init: R, f: impl Fn(R,T) -> R, it: impl DoubleEndedIterator<It
 /// 
 /// * `init`: initial point of folding, must be same type with final result
 /// * `f`: f :: (R, T) -> R, fold function
-/// * `it`: [`DoubleEndedIterator`] T
-pub fn foldr<T,R>(init: R, f: impl Fn(R,T) -> R, it: impl DoubleEndedIterator<Item=T>) -> R {  
-    it.rev().fold(init, f)
+/// * `it`: [`IntoIterator`] T, into [`DoubleEndedIterator`]
+pub fn foldr<T,R>(init: R, f: impl Fn(R,T) -> R, it: impl IntoIterator<Item=T, IntoIter=impl DoubleEndedIterator<Item=T>>) -> R {
+    it.into_iter().rev().fold(init, f)
 }
 
 /// Used for filter [`Iterator`]<T>
@@ -56,9 +63,9 @@ pub fn foldr<T,R>(init: R, f: impl Fn(R,T) -> R, it: impl DoubleEndedIterator<It
 /// # Arguments
 /// 
 /// * `f`: f :: T -> [`bool`], function to filter item
-/// * `it`: [`Iterator`] to be filtered
-pub fn filter<T>(f: impl Fn(&T) -> bool, it: impl Iterator<Item=T>) -> impl Iterator<Item=T> {
-    it.filter(f)
+/// * `it`: [`IntoIterator`] to be filtered
+pub fn filter<T>(f: impl Fn(&T) -> bool, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    it.into_iter().filter(f)
 }
 
 /// Used for reverse filter [`Iterator`]<T>
@@ -66,27 +73,28 @@ pub fn filter<T>(f: impl Fn(&T) -> bool, it: impl Iterator<Item=T>) -> impl Iter
 /// # Arguments
 /// 
 /// * `f`: f :: T -> [`bool`], function to reverse filter item
-/// * `it`: [`Iterator`] to be filtered
-pub fn filter_not<T>(f: impl Fn(&T) -> bool, it: impl Iterator<Item=T>) -> impl Iterator<Item=T> {
-    it.filter(move |x| !f(x))
+/// * `it`: [`IntoIterator`] to be filtered
+pub fn filter_not<T>(f: impl Fn(&T) -> bool, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    it.into_iter().filter(move |x| !f(x))
 }
 
 /// Getting the first element of [`Iterator`]<T>
 ///
 /// # Arguments
 ///
-/// * `it`: [`Iterator`] T
-pub fn head<T>(mut it: impl Iterator<Item=T>) -> Option<T> {
-    it.next()
+/// * `it`: [`IntoIterator`] T
+pub fn head<T>(it: impl IntoIterator<Item=T>) -> Option<T> {
+    it.into_iter().next()
 }
 
 /// Getting all elements of [`Iterator`]<T> except first as [`Vec`]<T>
 ///
 /// # Arguments
 ///
-/// * `it`: [`Iterator`] T
-pub fn tail<T>(mut it: impl Iterator<Item=T>) -> Option<Vec<T>> {
-    if let Some(_) = it.next() {
+/// * `it`: [`IntoIterator`] T
+pub fn tail<T>(it: impl IntoIterator<Item=T>) -> Option<Vec<T>> {
+    let mut it = it.into_iter();
+    if it.next().is_some() {
         let mut ret = Vec::new();
         ret.extend(it);
         return Some(ret);
@@ -98,8 +106,8 @@ pub fn tail<T>(mut it: impl Iterator<Item=T>) -> Option<Vec<T>> {
 ///
 /// # Arguments
 ///
-/// * `it`: [`Iterator`] T
-pub fn last<T>(it: impl Iterator<Item=T>) -> Option<T> {
+/// * `it`: [`IntoIterator`] T
+pub fn last<T>(it: impl IntoIterator<Item=T>) -> Option<T> {
     let mut ret = None;
     for i in it {
         ret = Some(i);
@@ -111,8 +119,8 @@ pub fn last<T>(it: impl Iterator<Item=T>) -> Option<T> {
 ///
 /// # Arguments
 ///
-/// * `it`: [`Iterator`] T
-pub fn init<T>(it: impl Iterator<Item=T>) -> Option<Vec<T>> {
+/// * `it`: [`IntoIterator`] T
+pub fn init<T>(it: impl IntoIterator<Item=T>) -> Option<Vec<T>> {
     let mut ret = Vec::new();
     ret.extend(it);
     let size = ret.len();
@@ -128,8 +136,9 @@ pub fn init<T>(it: impl Iterator<Item=T>) -> Option<Vec<T>> {
 /// # Arguments
 ///
 /// * `n`: elements count to skip
-/// * `it`: [`Iterator`] T
-pub fn skip<T>(n: usize, mut it: impl Iterator<Item=T>) -> Vec<T> {
+/// * `it`: [`IntoIterator`] T
+pub fn skip<T>(n: usize, it: impl IntoIterator<Item=T>) -> Vec<T> {
+    let mut it = it.into_iter();
     for _ in 0..n {
         it.next();
     }
@@ -143,8 +152,9 @@ pub fn skip<T>(n: usize, mut it: impl Iterator<Item=T>) -> Vec<T> {
 /// # Arguments
 ///
 /// * `n`: elements count to take
-/// * `it`: [`Iterator`] T
-pub fn take<T>(n: usize, mut it: impl Iterator<Item=T>) -> Vec<T> {
+/// * `it`: [`IntoIterator`] T
+pub fn take<T>(n: usize, it: impl IntoIterator<Item=T>) -> Vec<T> {
+    let mut it = it.into_iter();
     let mut ret = Vec::new();
     for _ in 0..n {
         match it.next() {
@@ -157,28 +167,31 @@ pub fn take<T>(n: usize, mut it: impl Iterator<Item=T>) -> Vec<T> {
 
 /// Invoke [`Mul`] over a [`Iterator`]<T>
 ///
+/// T must impl [`Product`], which is provided by std for all integer and float types, e.g. `i32`, `i64`, `f64`.
+/// Result is the same type as item, so iterator of references should be `cloned` first
+///
 /// # Arguments
 ///
-/// * `it`: [`Iterator`] T
-pub fn product<T: Product>(it: impl Iterator<Item=T>) -> T {
-    it.product()
+/// * `it`: [`IntoIterator`] T
+pub fn product<T: Product>(it: impl IntoIterator<Item=T>) -> T {
+    it.into_iter().product()
 }
 
 /// Get the length of [`Iterator`]<T>
 ///
 /// # Arguments
 ///
-/// * `it`: [`Iterator`] T
-pub fn length<T>(it: impl Iterator<Item=T>) -> usize {
-    it.count()
+/// * `it`: [`IntoIterator`] T
+pub fn length<T>(it: impl IntoIterator<Item=T>) -> usize {
+    it.into_iter().count()
 }
 
 /// Reverse a [`Iterator`]<T>
 ///
 /// # Arguments
 ///
-/// * `it`: [`Iterator`] T
-pub fn reverse<T>(it: impl Iterator<Item=T>) -> Vec<T> {
+/// * `it`: [`IntoIterator`] T
+pub fn reverse<T>(it: impl IntoIterator<Item=T>) -> Vec<T> {
     let mut ret = Vec::new();
     ret.extend(it);
     ret.reverse();
@@ -189,8 +202,8 @@ pub fn reverse<T>(it: impl Iterator<Item=T>) -> Vec<T> {
 ///
 /// # Arguments
 ///
-/// * `it`: [`Iterator`] T
-pub fn concat<T>(it1: impl Iterator<Item=T>, it2: impl Iterator<Item=T>) -> Vec<T> {
+/// * `it`: [`IntoIterator`] T
+pub fn concat<T>(it1: impl IntoIterator<Item=T>, it2: impl IntoIterator<Item=T>) -> Vec<T> {
     let mut ret = Vec::new();
     ret.extend(it1);
     ret.extend(it2);
@@ -210,18 +223,18 @@ pub fn id<T>(x: T) -> T {
 ///
 /// # Arguments
 ///
-/// * `it`: [`Iterator`] T
-pub fn min<T: Ord>(it: impl Iterator<Item=T>) -> Option<T> {
-    it.min()
+/// * `it`: [`IntoIterator`] T
+pub fn min<T: Ord>(it: impl IntoIterator<Item=T>) -> Option<T> {
+    it.into_iter().min()
 }
 
 /// Get max value of [`Iterator`]<T>
 ///
 /// # Arguments
 ///
-/// * `it`: [`Iterator`] T
-pub fn max<T: Ord>(it: impl Iterator<Item=T>) -> Option<T> {
-    it.max()
+/// * `it`: [`IntoIterator`] T
+pub fn max<T: Ord>(it: impl IntoIterator<Item=T>) -> Option<T> {
+    it.into_iter().max()
 }
 
 /// Unary operator -
@@ -285,16 +298,16 @@ pub fn div<T: Div<Output=T>>(x: T, y: T) -> T {
 ///
 /// # Arguments
 /// * `key`: [`Hash`]+[`Eq`] K => K
-/// * `it`: [`Iterator`] (K,V)
-pub fn find<K:Hash+Eq,V>(key: K, mut it: impl Iterator<Item=(K,V)>) -> Option<(K,V)> {
-    it.find(move |(x,_)| *x == key)
+/// * `it`: [`IntoIterator`] (K,V)
+pub fn find<K:Hash+Eq,V>(key: K, it: impl IntoIterator<Item=(K,V)>) -> Option<(K,V)> {
+    it.into_iter().find(move |(x,_)| *x == key)
 }
 
 /// sort an [`Iterator`]<T>, T must impl Ord
 ///
 /// # Arguments
-/// * `it`: [`Iterator`] T
-pub fn sorted<T: Ord>(it: impl Iterator<Item=T>) -> impl Iterator<Item=T> {
+/// * `it`: [`IntoIterator`] T
+pub fn sorted<T: Ord>(it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
     let mut tmp = Vec::new();
     tmp.extend(it);
     tmp.sort_unstable();
@@ -305,8 +318,8 @@ pub fn sorted<T: Ord>(it: impl Iterator<Item=T>) -> impl Iterator<Item=T> {
 ///
 /// # Arguments
 /// * `f`: (&T -> &T) -> [`Ordering`]
-/// *`it`: [`Iterator`] T
-pub fn sorted_by<T>(f: impl Fn(&T,&T) -> Ordering, it: impl Iterator<Item=T>) -> impl Iterator<Item=T> {
+/// * `it`: [`IntoIterator`] T
+pub fn sorted_by<T>(f: impl Fn(&T,&T) -> Ordering, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
     let mut tmp = Vec::new();
     tmp.extend(it);
     tmp.sort_unstable_by(f);
@@ -316,22 +329,1140 @@ pub fn sorted_by<T>(f: impl Fn(&T,&T) -> Ordering, it: impl Iterator<Item=T>) ->
 /// [`Zip`] two iterator into one iterator which return same index of item on two iterators
 ///
 /// # Arguments
-/// * `it1`: [`Iterator`] T
-/// * `it2`: [`Iterator`] T
-pub fn zip<T,U>(it1: impl Iterator<Item=T>, it2: impl Iterator<Item=U>) -> Zip<impl Iterator<Item=T>, impl Iterator<Item=U>> {
+/// * `it1`: [`IntoIterator`] T
+/// * `it2`: [`IntoIterator`] U
+pub fn zip<T,U>(it1: impl IntoIterator<Item=T>, it2: impl IntoIterator<Item=U>) -> Zip<impl Iterator<Item=T>, impl Iterator<Item=U>> {
     let mut ret1 = Vec::new();
     ret1.extend(it1);
     let mut ret2 = Vec::new();
     ret2.extend(it2);
-    ret1.into_iter().zip(ret2.into_iter())
+    ret1.into_iter().zip(ret2)
 }
 
 /// [`Zip`] two iterator into one iterator by mapping with a function
 ///
 /// # Arguments
 /// * `f`: Fn (T,U) -> V
-/// * `it1`: [`Iterator`] T
-/// * `it2`: [`Iterator`] U
-pub fn zip_with<T,U,V>(f: impl Fn((T,U)) -> V, it1: impl Iterator<Item=T>, it2: impl Iterator<Item=U>) -> impl Iterator<Item=V> {
+/// * `it1`: [`IntoIterator`] T
+/// * `it2`: [`IntoIterator`] U
+pub fn zip_with<T,U,V>(f: impl Fn((T,U)) -> V, it1: impl IntoIterator<Item=T>, it2: impl IntoIterator<Item=U>) -> impl Iterator<Item=V> {
     zip(it1,it2).map(f)
+}
+
+/// Take every n-th element of [`Iterator`]<T>, starting from the first one
+///
+/// # Arguments
+/// * `n`: step between two taken elements
+/// * `it`: [`IntoIterator`] T
+///
+/// # Panics
+/// if n == 0
+pub fn stride<T>(n: usize, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    every(n, 0, it)
+}
+
+/// Lazily take elements at index offset, offset + step, offset + 2 * step... of [`Iterator`]<T>,
+/// [stride](basic_fn::fun::stride) with a starting offset
+///
+/// # Arguments
+/// * `step`: [`usize`], step between two taken elements, must be >= 1
+/// * `offset`: [`usize`], index of the first taken element
+/// * `it`: [`IntoIterator`] T
+///
+/// # Panics
+/// if step == 0
+pub fn every<T>(step: usize, offset: usize, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    assert!(step > 0, "every requires a step greater than 0");
+    it.into_iter().skip(offset).step_by(step)
+}
+
+/// Take elements on even index (0, 2, 4...) of [`Iterator`]<T>
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T
+pub fn take_even<T>(it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    stride(2, it)
+}
+
+/// Take elements on odd index (1, 3, 5...) of [`Iterator`]<T>
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T
+pub fn take_odd<T>(it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    stride(2, it.into_iter().skip(1))
+}
+
+/// Prepend an element to [`Iterator`]<T>, i.e. x : it
+///
+/// # Arguments
+/// * `x`: T
+/// * `it`: [`IntoIterator`] T
+pub fn cons<T>(x: T, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    once(x).chain(it)
+}
+
+/// Append an element to the end of [`Iterator`]<T>
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T
+/// * `x`: T
+pub fn snoc<T>(it: impl IntoIterator<Item=T>, x: T) -> impl Iterator<Item=T> {
+    it.into_iter().chain(once(x))
+}
+
+/// Split [`Iterator`]<T> into its first element and the remaining lazy [`Iterator`]<T>
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T
+pub fn uncons<T>(it: impl IntoIterator<Item=T>) -> Option<(T, impl Iterator<Item=T>)> {
+    let mut it = it.into_iter();
+    it.next().map(move |x| (x, it))
+}
+
+/// Generate (row, col) coordinates of a rows x cols grid in row-major order
+///
+/// # Arguments
+/// * `rows`: [`usize`] count of rows
+/// * `cols`: [`usize`] count of columns
+pub fn grid_indices(rows: usize, cols: usize) -> impl Iterator<Item=(usize, usize)> {
+    (0..rows).flat_map(move |r| (0..cols).map(move |c| (r, c)))
+}
+
+/// Apply function on value, i.e. f(x)
+///
+/// # Arguments
+/// * `f`: f :: T -> R
+/// * `x`: T
+pub fn apply<T,R>(f: impl FnOnce(T) -> R, x: T) -> R {
+    f(x)
+}
+
+/// Memorize a value and apply any function passed later on it
+///
+/// # Arguments
+/// * `x`: T, cloned on each invoke
+pub fn apply_to<T: Clone, R, F: FnOnce(T) -> R>(x: T) -> impl Fn(F) -> R {
+    move |f| f(x.clone())
+}
+
+/// Zip two [`IntoIterator`] and map each pair by a function, stop at the shorter one
+///
+/// # Arguments
+/// * `f`: f :: (A, B) -> C
+/// * `a`: [`IntoIterator`] A, e.g. array, [`Vec`] or range
+/// * `b`: [`IntoIterator`] B
+pub fn zip_map<A,B,C>(f: impl Fn(A,B) -> C, a: impl IntoIterator<Item=A>, b: impl IntoIterator<Item=B>) -> impl Iterator<Item=C> {
+    a.into_iter().zip(b).map(move |(x, y)| f(x, y))
+}
+
+/// Zip two [`IntoIterator`] until both of them are exhausted, exhausted side is filled by [`None`]
+///
+/// # Arguments
+/// * `a`: [`IntoIterator`] A
+/// * `b`: [`IntoIterator`] B
+pub fn zip_longest<A,B>(a: impl IntoIterator<Item=A>, b: impl IntoIterator<Item=B>) -> impl Iterator<Item=(Option<A>, Option<B>)> {
+    let mut a = a.into_iter().fuse();
+    let mut b = b.into_iter().fuse();
+    from_fn(move || match (a.next(), b.next()) {
+        (None, None) => None,
+        pair => Some(pair)
+    })
+}
+
+/// Zip three [`IntoIterator`] until all of them are exhausted, exhausted sides are filled by [`None`]
+///
+/// # Arguments
+/// * `a`: [`IntoIterator`] A
+/// * `b`: [`IntoIterator`] B
+/// * `c`: [`IntoIterator`] C
+pub fn zip3_longest<A,B,C>(a: impl IntoIterator<Item=A>, b: impl IntoIterator<Item=B>, c: impl IntoIterator<Item=C>) -> impl Iterator<Item=(Option<A>, Option<B>, Option<C>)> {
+    let mut a = a.into_iter().fuse();
+    let mut b = b.into_iter().fuse();
+    let mut c = c.into_iter().fuse();
+    from_fn(move || match (a.next(), b.next(), c.next()) {
+        (None, None, None) => None,
+        triple => Some(triple)
+    })
+}
+
+/// Zip two [`IntoIterator`] until both of them are exhausted, aligned pairs are mapped by both_fn
+/// and leftover elements of the longer side by left_fn or right_fn
+///
+/// # Arguments
+/// * `both_fn`: both_fn :: (A, B) -> C
+/// * `left_fn`: left_fn :: A -> C
+/// * `right_fn`: right_fn :: B -> C
+/// * `a`: [`IntoIterator`] A
+/// * `b`: [`IntoIterator`] B
+pub fn zip_longest_with<A,B,C>(both_fn: impl Fn(A, B) -> C, left_fn: impl Fn(A) -> C, right_fn: impl Fn(B) -> C,
+                               a: impl IntoIterator<Item=A>, b: impl IntoIterator<Item=B>) -> Vec<C> {
+    zip_longest(a, b).map(|pair| match pair {
+        (Some(x), Some(y)) => both_fn(x, y),
+        (Some(x), None) => left_fn(x),
+        (None, Some(y)) => right_fn(y),
+        (None, None) => unreachable!()
+    }).collect()
+}
+
+/// Zip two [`IntoIterator`] until both of them are exhausted, exhausted side is filled by [`Default`]
+///
+/// # Arguments
+/// * `a`: [`IntoIterator`] A, A must impl [`Default`]
+/// * `b`: [`IntoIterator`] B, B must impl [`Default`]
+pub fn zip_longest_default<A: Default, B: Default>(a: impl IntoIterator<Item=A>, b: impl IntoIterator<Item=B>) -> impl Iterator<Item=(A, B)> {
+    zip_longest(a, b).map(|(x, y)| (x.unwrap_or_default(), y.unwrap_or_default()))
+}
+
+/// Right-associative reduce seeded by the last element, i.e. f(x1, f(x2, f(x3, x4)))
+///
+/// # Arguments
+/// * `f`: f :: (T, T) -> T, first argument is the element, second one is the accumulated value
+/// * `it`: [`IntoIterator`] T, into [`DoubleEndedIterator`]
+pub fn reduce_right<T>(f: impl Fn(T,T) -> T, it: impl IntoIterator<Item=T, IntoIter=impl DoubleEndedIterator<Item=T>>) -> Option<T> {
+    let mut it = it.into_iter().rev();
+    let init = it.next()?;
+    Some(it.fold(init, |acc, x| f(x, acc)))
+}
+
+/// Map and collect the longest prefix satisfying predicate, return it with the remaining lazy [`Iterator`]<T>,
+/// the remaining part starts from the first element failing the predicate
+///
+/// # Arguments
+/// * `pred`: pred :: &T -> [`bool`]
+/// * `f`: f :: T -> U
+/// * `it`: [`IntoIterator`] T
+pub fn take_while_map<T,U>(pred: impl Fn(&T) -> bool, f: impl Fn(T) -> U, it: impl IntoIterator<Item=T>) -> (Vec<U>, impl Iterator<Item=T>) {
+    let mut it = it.into_iter().peekable();
+    let mut ret = Vec::new();
+    while let Some(x) = it.next_if(&pred) {
+        ret.push(f(x));
+    }
+    (ret, it)
+}
+
+/// Get successive pairs of [`IntoIterator`]<T>, i.e. (x0, x1), (x1, x2)...
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T, T must impl [`Clone`]
+pub fn pairwise<T: Clone>(it: impl IntoIterator<Item=T>) -> impl Iterator<Item=(T, T)> {
+    let mut it = it.into_iter();
+    let mut prev = it.next();
+    from_fn(move || {
+        let next = it.next()?;
+        let p = prev.replace(next.clone())?;
+        Some((p, next))
+    })
+}
+
+/// Get successive differences of [`IntoIterator`]<T>, i.e. x1 - x0, x2 - x1...
+///
+/// Result is one element shorter than the input, see [integrate](basic_fn::fun::integrate) for the inverse
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T, T must impl [`Sub`] and [`Clone`]
+pub fn deltas<T: Clone + Sub<Output=T>>(it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    pairwise(it).map(|(a, b)| b - a)
+}
+
+/// Get prefix sums starting from `init`, i.e. init, init + x0, init + x0 + x1...
+///
+/// Result is one element longer than the input, so `integrate(x0, deltas(xs))` recovers `xs`
+///
+/// # Arguments
+/// * `init`: T, first element of result
+/// * `it`: [`IntoIterator`] T, T must impl [`Add`] and [`Clone`]
+pub fn integrate<T: Clone + Add<Output=T>>(init: T, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    once(init.clone()).chain(it.into_iter().scan(init, |acc, x| {
+        *acc = acc.clone() + x;
+        Some(acc.clone())
+    }))
+}
+
+/// Get lengths of runs of consecutive equal elements, element values are discarded
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T, T must impl [`PartialEq`]
+pub fn run_lengths<T: PartialEq>(it: impl IntoIterator<Item=T>) -> impl Iterator<Item=usize> {
+    let mut it = it.into_iter().peekable();
+    from_fn(move || {
+        let x = it.next()?;
+        let mut n = 1;
+        while it.next_if(|y| *y == x).is_some() {
+            n += 1;
+        }
+        Some(n)
+    })
+}
+
+/// Apply each function on the value of same index, stop at the shorter one
+///
+/// # Arguments
+/// * `fns`: [`IntoIterator`] F, F :: T -> U
+/// * `args`: [`IntoIterator`] T
+pub fn zip_apply<T,U,F: FnOnce(T) -> U>(fns: impl IntoIterator<Item=F>, args: impl IntoIterator<Item=T>) -> impl Iterator<Item=U> {
+    fns.into_iter().zip(args).map(|(f, x)| f(x))
+}
+
+/// Map each element into a [`Monoid`] and combine them from the left
+///
+/// # Arguments
+/// * `f`: f :: T -> M, M must impl [`Monoid`]
+/// * `it`: [`IntoIterator`] T
+pub fn fold_map<T, M: Monoid>(f: impl Fn(T) -> M, it: impl IntoIterator<Item=T>) -> M {
+    it.into_iter().map(f).fold(M::empty(), M::combine)
+}
+
+/// Map each element with its index into a [`Monoid`] and combine them from the left
+///
+/// # Arguments
+/// * `f`: f :: ([`usize`], T) -> M, M must impl [`Monoid`]
+/// * `it`: [`IntoIterator`] T
+pub fn fold_map_indexed<T, M: Monoid>(f: impl Fn(usize, T) -> M, it: impl IntoIterator<Item=T>) -> M {
+    it.into_iter().enumerate().map(|(i, x)| f(i, x)).fold(M::empty(), M::combine)
+}
+
+/// Find the first element which has appeared before, stop consuming as soon as it is found
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T, T must impl [`Hash`], [`Eq`] and [`Clone`]
+pub fn first_duplicate<T: Hash + Eq + Clone>(it: impl IntoIterator<Item=T>) -> Option<T> {
+    let mut seen = HashSet::new();
+    it.into_iter().find(|x| !seen.insert(x.clone()))
+}
+
+/// Flatten one level of nesting, e.g. [[T]] -> [T]
+///
+/// Depth of nesting is part of the type in Rust, so use [flatten2](basic_fn::fun::flatten2)
+/// and [flatten3](basic_fn::fun::flatten3) for deeper nesting
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] I, I must impl [`IntoIterator`]
+pub fn flatten<I: IntoIterator>(it: impl IntoIterator<Item=I>) -> impl Iterator<Item=I::Item> {
+    it.into_iter().flatten()
+}
+
+/// Flatten two levels of nesting, e.g. [[[T]]] -> [T]
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] of nested [`IntoIterator`] in 2 levels
+pub fn flatten2<I, J>(it: impl IntoIterator<Item=I>) -> impl Iterator<Item=J::Item>
+    where I: IntoIterator<Item=J>, J: IntoIterator
+{
+    flatten(flatten(it))
+}
+
+/// Flatten three levels of nesting, e.g. [[[[T]]]] -> [T]
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] of nested [`IntoIterator`] in 3 levels
+pub fn flatten3<I, J, K>(it: impl IntoIterator<Item=I>) -> impl Iterator<Item=K::Item>
+    where I: IntoIterator<Item=J>, J: IntoIterator<Item=K>, K: IntoIterator
+{
+    flatten(flatten2(it))
+}
+
+/// Element-wise sum of two [`IntoIterator`], stop at the shorter one
+///
+/// # Arguments
+/// * `a`: [`IntoIterator`] T, T must impl [`Add`]
+/// * `b`: [`IntoIterator`] T
+pub fn zip_sum<T: Add<Output=T>>(a: impl IntoIterator<Item=T>, b: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    zip_map(add, a, b)
+}
+
+/// Element-wise difference of two [`IntoIterator`], i.e. a - b, stop at the shorter one
+///
+/// # Arguments
+/// * `a`: [`IntoIterator`] T, T must impl [`Sub`]
+/// * `b`: [`IntoIterator`] T
+pub fn zip_sub<T: Sub<Output=T>>(a: impl IntoIterator<Item=T>, b: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    zip_map(sub, a, b)
+}
+
+/// Multiply each element by a factor
+///
+/// # Arguments
+/// * `factor`: T, T must impl [`Mul`] and [`Clone`]
+/// * `it`: [`IntoIterator`] T
+pub fn scale<T: Mul<Output=T> + Clone>(factor: T, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    it.into_iter().map(move |x| x * factor.clone())
+}
+
+/// Add a scalar to each element
+///
+/// # Arguments
+/// * `k`: T, T must impl [`Add`] and [`Clone`]
+/// * `it`: [`IntoIterator`] T
+pub fn add_scalar<T: Add<Output=T> + Clone>(k: T, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    it.into_iter().map(move |x| x + k.clone())
+}
+
+/// Sum of each sliding window of width n, in O(total) time by a running sum.
+/// Yield nothing if there are fewer than n elements.
+///
+/// # Arguments
+/// * `n`: [`usize`], width of window
+/// * `it`: [`IntoIterator`] T, T must impl [`Add`], [`Sub`] and [`Clone`]
+///
+/// # Panics
+/// if n == 0
+pub fn windowed_sum<T>(n: usize, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T>
+    where T: Add<Output=T> + Sub<Output=T> + Clone
+{
+    assert!(n > 0, "window width must be positive");
+    let mut it = it.into_iter();
+    let mut window = VecDeque::with_capacity(n + 1);
+    let mut sum: Option<T> = None;
+    from_fn(move || loop {
+        let x = it.next()?;
+        window.push_back(x.clone());
+        let mut s = match sum.take() {
+            None => x,
+            Some(s) => s + x
+        };
+        if window.len() > n {
+            s = s - window.pop_front()?;
+        }
+        sum = Some(s.clone());
+        if window.len() == n {
+            return Some(s);
+        }
+    })
+}
+
+/// Mean of each sliding window of width n as [`f64`], based on [windowed_sum](basic_fn::fun::windowed_sum).
+/// Yield nothing if there are fewer than n elements.
+///
+/// # Arguments
+/// * `n`: [`usize`], width of window
+/// * `it`: [`IntoIterator`] T, T must impl [`Into`]<[`f64`]>
+///
+/// # Panics
+/// if n == 0
+pub fn moving_average<T: Into<f64>>(n: usize, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=f64> {
+    windowed_sum(n, it.into_iter().map(Into::into)).map(move |s: f64| s / n as f64)
+}
+
+/// Clamp each element into [lo, hi]
+///
+/// # Arguments
+/// * `lo`: T, lower bound, T must impl [`PartialOrd`] and [`Clone`]
+/// * `hi`: T, upper bound
+/// * `it`: [`IntoIterator`] T
+pub fn clamp_iter<T: PartialOrd + Clone>(lo: T, hi: T, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    clip_above(hi, clip_below(lo, it))
+}
+
+/// Replace each element less than lo by lo
+///
+/// # Arguments
+/// * `lo`: T, lower bound, T must impl [`PartialOrd`] and [`Clone`]
+/// * `it`: [`IntoIterator`] T
+pub fn clip_below<T: PartialOrd + Clone>(lo: T, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    it.into_iter().map(move |x| if x < lo { lo.clone() } else { x })
+}
+
+/// Replace each element greater than hi by hi
+///
+/// # Arguments
+/// * `hi`: T, upper bound, T must impl [`PartialOrd`] and [`Clone`]
+/// * `it`: [`IntoIterator`] T
+pub fn clip_above<T: PartialOrd + Clone>(hi: T, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    it.into_iter().map(move |x| if x > hi { hi.clone() } else { x })
+}
+
+/// Get the indices which would sort the elements ascending, the order of equal elements is kept
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T, T must impl [`Ord`]
+pub fn argsort<T: Ord>(it: impl IntoIterator<Item=T>) -> Vec<usize> {
+    argsort_by(Ord::cmp, it)
+}
+
+/// Get the indices which would sort the elements by function, the order of equal elements is kept
+///
+/// # Arguments
+/// * `f`: (&T -> &T) -> [`Ordering`]
+/// * `it`: [`IntoIterator`] T
+pub fn argsort_by<T>(f: impl Fn(&T,&T) -> Ordering, it: impl IntoIterator<Item=T>) -> Vec<usize> {
+    let tmp = it.into_iter().collect::<Vec<_>>();
+    let mut ret = (0..tmp.len()).collect::<Vec<_>>();
+    ret.sort_by(|&i, &j| f(&tmp[i], &tmp[j]));
+    ret
+}
+
+/// Place elements according to the permutation indices, i.e. ret[k] = it[indices[k]]
+///
+/// # Arguments
+/// * `indices`: [`IntoIterator`] [`usize`]
+/// * `it`: [`IntoIterator`] T, T must impl [`Clone`]
+///
+/// # Panics
+/// if any index is out of range
+pub fn reorder<T: Clone>(indices: impl IntoIterator<Item=usize>, it: impl IntoIterator<Item=T>) -> Vec<T> {
+    let tmp = it.into_iter().collect::<Vec<_>>();
+    indices.into_iter().map(|i| tmp[i].clone()).collect()
+}
+
+/// Get 1-based rank of each element, ties get the average of their ranks (fractional ranking)
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T, T must impl [`Ord`]
+pub fn rank<T: Ord>(it: impl IntoIterator<Item=T>) -> Vec<f64> {
+    let tmp = it.into_iter().collect::<Vec<_>>();
+    let indices = argsort(&tmp);
+    let mut ret = vec![0.0; tmp.len()];
+    let mut start = 0;
+    while start < indices.len() {
+        let mut end = start + 1;
+        while end < indices.len() && tmp[indices[end]] == tmp[indices[start]] {
+            end += 1;
+        }
+        // positions start+1..=end share the average rank
+        let r = (start + 1 + end) as f64 / 2.0;
+        for &i in &indices[start..end] {
+            ret[i] = r;
+        }
+        start = end;
+    }
+    ret
+}
+
+/// Get 1-based dense rank of each element, ties get the same rank and no rank is skipped
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T, T must impl [`Ord`]
+pub fn dense_rank<T: Ord>(it: impl IntoIterator<Item=T>) -> Vec<usize> {
+    let tmp = it.into_iter().collect::<Vec<_>>();
+    let indices = argsort(&tmp);
+    let mut ret = vec![0; tmp.len()];
+    let mut r = 0;
+    for (k, &i) in indices.iter().enumerate() {
+        if k == 0 || tmp[i] != tmp[indices[k - 1]] {
+            r += 1;
+        }
+        ret[i] = r;
+    }
+    ret
+}
+
+/// Compare two [`IntoIterator`] lexicographically, stop at the first different pair.
+/// If one is a prefix of the other, the shorter one is less.
+///
+/// # Arguments
+/// * `a`: [`IntoIterator`] T, T must impl [`Ord`]
+/// * `b`: [`IntoIterator`] T
+pub fn lex_cmp<T: Ord>(a: impl IntoIterator<Item=T>, b: impl IntoIterator<Item=T>) -> Ordering {
+    let mut a = a.into_iter();
+    let mut b = b.into_iter();
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match x.cmp(&y) {
+                Ordering::Equal => continue,
+                ord => return ord
+            }
+        }
+    }
+}
+
+/// Count positions where two sequences differ
+///
+/// # Arguments
+/// * `a`: [`IntoIterator`] T, T must impl [`PartialEq`]
+/// * `b`: [`IntoIterator`] T
+///
+/// # Panics
+/// if a and b have different lengths
+pub fn hamming<T: PartialEq>(a: impl IntoIterator<Item=T>, b: impl IntoIterator<Item=T>) -> usize {
+    zip_longest(a, b).filter(|pair| match *pair {
+        (Some(ref x), Some(ref y)) => x != y,
+        _ => panic!("hamming distance requires sequences of equal length")
+    }).count()
+}
+
+/// Edit distance (Levenshtein distance) of two sequences, i.e. the minimum count of
+/// insertions, deletions and substitutions to transform a into b.
+/// Takes O(len_a * len_b) time and O(len_a) extra space, as only a is collected and b is streamed,
+/// so pass the shorter sequence as a (the distance is symmetric)
+///
+/// # Arguments
+/// * `a`: [`IntoIterator`] T, T must impl [`PartialEq`]
+/// * `b`: [`IntoIterator`] T
+pub fn levenshtein<T: PartialEq>(a: impl IntoIterator<Item=T>, b: impl IntoIterator<Item=T>) -> usize {
+    let a = a.into_iter().collect::<Vec<_>>();
+    // row[j] is the distance between a[..j] and the consumed prefix of b
+    let mut row = (0..=a.len()).collect::<Vec<_>>();
+    for (i, y) in b.into_iter().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, x) in a.iter().enumerate() {
+            let cost = if *x == y { 0 } else { 1 };
+            let next = (diag + cost).min(row[j] + 1).min(row[j + 1] + 1);
+            diag = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[a.len()]
+}
+
+/// Longest common subsequence of two sequences by dynamic programming in O(len_a * len_b).
+///
+/// When several subsequences have the longest length, the backtracking prefers skipping elements of a,
+/// e.g. lcs([1,2,3,4], [2,4,3]) = [2,4] rather than [2,3]
+///
+/// # Arguments
+/// * `a`: [`IntoIterator`] T, T must impl [`PartialEq`] and [`Clone`]
+/// * `b`: [`IntoIterator`] T
+pub fn lcs<T: PartialEq + Clone>(a: impl IntoIterator<Item=T>, b: impl IntoIterator<Item=T>) -> Vec<T> {
+    let a = a.into_iter().collect::<Vec<_>>();
+    let b = b.into_iter().collect::<Vec<_>>();
+    let table = lcs_table(&a, &b);
+    let (mut i, mut j) = (0, 0);
+    let mut ret = Vec::new();
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            ret.push(a[i].clone());
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    ret
+}
+
+/// table[i][j] is the length of the longest common subsequence of a[i..] and b[j..]
+pub(crate) fn lcs_table<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Vec<usize>> {
+    let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            table[i][j] = if a[i] == b[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+    table
+}
+
+/// Zip two [`IntoIterator`] which must have equal lengths, unlike [zip](basic_fn::fun::zip)
+/// which stops at the shorter one silently
+///
+/// # Arguments
+/// * `a`: [`IntoIterator`] A
+/// * `b`: [`IntoIterator`] B
+///
+/// # Panics
+/// when one side is exhausted before the other one, the panic happens lazily on iterating
+pub fn zip_eq<A,B>(a: impl IntoIterator<Item=A>, b: impl IntoIterator<Item=B>) -> impl Iterator<Item=(A, B)> {
+    zip_longest(a, b).map(|pair| match pair {
+        (Some(x), Some(y)) => (x, y),
+        _ => panic!("zip_eq requires iterators of equal length")
+    })
+}
+
+/// Pair the sliding windows of width n of two slices, stop at the shorter one.
+/// Slices are taken instead of iterators so that windows can be borrowed without copying.
+///
+/// # Arguments
+/// * `n`: [`usize`], width of window
+/// * `a`: &[A]
+/// * `b`: &[B]
+///
+/// # Panics
+/// if n == 0
+pub fn co_windows<'a, A, B>(n: usize, a: &'a [A], b: &'a [B]) -> impl Iterator<Item=(&'a [A], &'a [B])> {
+    a.windows(n).zip(b.windows(n))
+}
+
+/// Thread a state from the left while mapping each element, like `mapAccumL` of Haskell
+///
+/// # Arguments
+/// * `init`: S, initial state
+/// * `f`: f :: (S, T) -> (S, U), return the next state and the output
+/// * `it`: [`IntoIterator`] T
+pub fn mapaccum<S,T,U>(init: S, f: impl Fn(S,T) -> (S, U), it: impl IntoIterator<Item=T>) -> (S, Vec<U>) {
+    let mut ret = Vec::new();
+    let state = it.into_iter().fold(init, |s, x| {
+        let (next, y) = f(s, x);
+        ret.push(y);
+        next
+    });
+    (state, ret)
+}
+
+/// Fold in batches of n elements, f is called once per chunk with a slice (the last chunk may be shorter),
+/// which saves per element overhead for accumulators able to process a slice at once
+///
+/// # Arguments
+/// * `n`: [`usize`], size of chunk
+/// * `init`: R, initial value of folding
+/// * `f`: f :: (R, &[T]) -> R
+/// * `it`: [`IntoIterator`] T
+///
+/// # Panics
+/// if n == 0
+pub fn chunked_fold<T,R>(n: usize, init: R, f: impl Fn(R, &[T]) -> R, it: impl IntoIterator<Item=T>) -> R {
+    assert!(n > 0, "chunked_fold requires a chunk size greater than 0");
+    let mut buf = Vec::with_capacity(n);
+    let mut acc = init;
+    for x in it {
+        buf.push(x);
+        if buf.len() == n {
+            acc = f(acc, &buf);
+            buf.clear();
+        }
+    }
+    if !buf.is_empty() {
+        acc = f(acc, &buf);
+    }
+    acc
+}
+
+/// Split into maximal non-decreasing runs, a new run starts whenever an element
+/// is strictly less than its predecessor
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T, T must impl [`Ord`]
+pub fn monotonic_runs<T: Ord>(it: impl IntoIterator<Item=T>) -> Vec<Vec<T>> {
+    monotonic_runs_by(T::cmp, it)
+}
+
+fn monotonic_runs_by<T>(cmp: impl Fn(&T, &T) -> Ordering, it: impl IntoIterator<Item=T>) -> Vec<Vec<T>> {
+    let mut ret: Vec<Vec<T>> = Vec::new();
+    for x in it {
+        match ret.last_mut() {
+            Some(run) if run.last().is_some_and(|last| cmp(last, &x) != Ordering::Greater) => run.push(x),
+            _ => ret.push(vec![x])
+        }
+    }
+    ret
+}
+
+/// Lazily merge two sorted [`IntoIterator`]s into one sorted iterator, on ties elements of `a` come first
+///
+/// # Arguments
+/// * `a`: [`IntoIterator`] T, sorted, T must impl [`Ord`]
+/// * `b`: [`IntoIterator`] T, sorted
+pub fn merge<T: Ord>(a: impl IntoIterator<Item=T>, b: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    merge_by(T::cmp, a, b)
+}
+
+/// Lazily merge two [`IntoIterator`]s sorted by cmp into one, on ties elements of `a` come first
+///
+/// # Arguments
+/// * `cmp`: cmp :: (&T, &T) -> [`Ordering`]
+/// * `a`: [`IntoIterator`] T, sorted by cmp
+/// * `b`: [`IntoIterator`] T, sorted by cmp
+pub fn merge_by<T>(cmp: impl Fn(&T, &T) -> Ordering, a: impl IntoIterator<Item=T>, b: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    let mut a = a.into_iter().peekable();
+    let mut b = b.into_iter().peekable();
+    from_fn(move || match (a.peek(), b.peek()) {
+        (Some(x), Some(y)) => if cmp(y, x) == Ordering::Less { b.next() } else { a.next() },
+        (Some(_), None) => a.next(),
+        _ => b.next()
+    })
+}
+
+/// Stable natural merge sort, returning a new sorted [`Vec`]. The input is cut into
+/// [monotonic_runs](basic_fn::fun::monotonic_runs) which are then merged pairwise,
+/// so nearly sorted input is handled in close to linear time
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T, T must impl [`Ord`]
+pub fn merge_sort<T: Ord>(it: impl IntoIterator<Item=T>) -> Vec<T> {
+    merge_sort_by(T::cmp, it)
+}
+
+/// Stable natural merge sort by cmp, see [merge_sort](basic_fn::fun::merge_sort)
+///
+/// # Arguments
+/// * `cmp`: cmp :: (&T, &T) -> [`Ordering`]
+/// * `it`: [`IntoIterator`] T
+pub fn merge_sort_by<T>(cmp: impl Fn(&T, &T) -> Ordering, it: impl IntoIterator<Item=T>) -> Vec<T> {
+    let mut runs = monotonic_runs_by(&cmp, it);
+    while runs.len() > 1 {
+        let mut next = Vec::with_capacity(runs.len().div_ceil(2));
+        let mut pairs = runs.into_iter();
+        while let Some(a) = pairs.next() {
+            match pairs.next() {
+                Some(b) => next.push(merge_by(&cmp, a, b).collect()),
+                None => next.push(a)
+            }
+        }
+        runs = next;
+    }
+    runs.pop().unwrap_or_default()
+}
+
+/// Binary search target in sorted slice, return `Ok(index)` if found, otherwise `Err(index)` where
+/// target can be inserted keeping slice sorted. With duplicates any matching index may be returned,
+/// use [bisect_left](basic_fn::fun::bisect_left) or [bisect_right](basic_fn::fun::bisect_right) for exact bound
+///
+/// # Arguments
+/// * `target`: T, T must impl [`Ord`]
+/// * `sorted`: [`AsRef`]<[T]>, e.g. [`Vec`] or slice, must be sorted
+pub fn binary_search<T: Ord>(target: T, sorted: impl AsRef<[T]>) -> Result<usize, usize> {
+    sorted.as_ref().binary_search(&target)
+}
+
+/// Binary search in sorted slice by f, f tells the ordering of an element relative to the target,
+/// see [binary_search](basic_fn::fun::binary_search)
+///
+/// # Arguments
+/// * `f`: f :: &T -> [`Ordering`]
+/// * `sorted`: [`AsRef`]<[T]>, must be sorted consistently with f
+pub fn binary_search_by<T>(f: impl Fn(&T) -> Ordering, sorted: impl AsRef<[T]>) -> Result<usize, usize> {
+    sorted.as_ref().binary_search_by(f)
+}
+
+/// Leftmost insertion point of target in sorted slice, i.e. index of first element not less than target,
+/// like `bisect.bisect_left` of Python
+///
+/// # Arguments
+/// * `target`: T, T must impl [`Ord`]
+/// * `sorted`: [`AsRef`]<[T]>, must be sorted
+pub fn bisect_left<T: Ord>(target: T, sorted: impl AsRef<[T]>) -> usize {
+    sorted.as_ref().partition_point(|x| *x < target)
+}
+
+/// Rightmost insertion point of target in sorted slice, i.e. index of first element greater than target,
+/// like `bisect.bisect_right` of Python
+///
+/// # Arguments
+/// * `target`: T, T must impl [`Ord`]
+/// * `sorted`: [`AsRef`]<[T]>, must be sorted
+pub fn bisect_right<T: Ord>(target: T, sorted: impl AsRef<[T]>) -> usize {
+    sorted.as_ref().partition_point(|x| *x <= target)
+}
+
+/// Lazily yield the maximum of each window of width n, nothing is yielded if there are less than n elements.
+/// A monotonic deque is kept so it takes amortized O(1) per element, O(len) in total instead of O(len * n)
+///
+/// # Arguments
+/// * `n`: [`usize`], width of window
+/// * `it`: [`IntoIterator`] T, T must impl [`Ord`] and [`Clone`]
+///
+/// # Panics
+/// if n == 0
+pub fn sliding_max<T: Ord + Clone>(n: usize, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    sliding_extreme(n, |back, x| back <= x, it)
+}
+
+/// Lazily yield the minimum of each window of width n, see [sliding_max](basic_fn::fun::sliding_max)
+///
+/// # Arguments
+/// * `n`: [`usize`], width of window
+/// * `it`: [`IntoIterator`] T, T must impl [`Ord`] and [`Clone`]
+///
+/// # Panics
+/// if n == 0
+pub fn sliding_min<T: Ord + Clone>(n: usize, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    sliding_extreme(n, |back, x| back >= x, it)
+}
+
+// `dominated(back, x)` tells whether back can never be the answer of a window once x arrives
+fn sliding_extreme<T: Clone>(n: usize, dominated: impl Fn(&T, &T) -> bool, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    assert!(n > 0, "sliding window requires a width greater than 0");
+    let mut it = it.into_iter().enumerate();
+    let mut deque: VecDeque<(usize, T)> = VecDeque::new();
+    from_fn(move || loop {
+        let (i, x) = it.next()?;
+        while deque.back().is_some_and(|(_, back)| dominated(back, &x)) {
+            deque.pop_back();
+        }
+        deque.push_back((i, x));
+        if deque.front().is_some_and(|&(j, _)| j + n <= i) {
+            deque.pop_front();
+        }
+        if i + 1 >= n {
+            return deque.front().map(|(_, x)| x.clone());
+        }
+    })
+}
+
+/// Fold over pairs of two [`IntoIterator`]s into a single accumulator, stop at the shorter one.
+/// Same as folding over [zip](basic_fn::fun::zip) without building the tuples
+///
+/// # Arguments
+/// * `init`: R, initial value of folding
+/// * `f`: f :: (R, A, B) -> R
+/// * `a`: [`IntoIterator`] A
+/// * `b`: [`IntoIterator`] B
+pub fn zip_reduce<A,B,R>(init: R, f: impl Fn(R, A, B) -> R, a: impl IntoIterator<Item=A>, b: impl IntoIterator<Item=B>) -> R {
+    let mut b = b.into_iter();
+    let mut acc = init;
+    for x in a {
+        match b.next() {
+            Some(y) => acc = f(acc, x, y),
+            None => break
+        }
+    }
+    acc
+}
+
+/// Split into n streams round-robin, element at index i goes to stream i % n.
+/// Streams are always n even if some of them are empty
+///
+/// # Arguments
+/// * `n`: [`usize`], count of streams
+/// * `it`: [`IntoIterator`] T
+///
+/// # Panics
+/// if n == 0
+pub fn deinterleave<T>(n: usize, it: impl IntoIterator<Item=T>) -> Vec<Vec<T>> {
+    assert!(n > 0, "deinterleave requires at least 1 stream");
+    let mut ret: Vec<Vec<T>> = (0..n).map(|_| Vec::new()).collect();
+    for (i, x) in it.into_iter().enumerate() {
+        ret[i % n].push(x);
+    }
+    ret
+}
+
+/// Run length encoding keeping everything of each run: the representative (first element),
+/// the length and the elements of the run
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T, T must impl [`PartialEq`] and [`Clone`]
+pub fn rle_full<T: PartialEq + Clone>(it: impl IntoIterator<Item=T>) -> Vec<(T, usize, Vec<T>)> {
+    rle_full_by(T::clone, it)
+}
+
+/// Run length encoding where consecutive elements of same key are in one run,
+/// the key is the representative and elements of a run may differ
+///
+/// # Arguments
+/// * `key`: key :: &T -> K, K must impl [`PartialEq`]
+/// * `it`: [`IntoIterator`] T
+pub fn rle_full_by<T, K: PartialEq>(key: impl Fn(&T) -> K, it: impl IntoIterator<Item=T>) -> Vec<(K, usize, Vec<T>)> {
+    let mut it = it.into_iter().peekable();
+    let mut ret = Vec::new();
+    while let Some(x) = it.next() {
+        let k = key(&x);
+        let mut items = vec![x];
+        while let Some(y) = it.next_if(|y| key(y) == k) {
+            items.push(y);
+        }
+        ret.push((k, items.len(), items));
+    }
+    ret
+}
+
+/// Lazily yield running left folds seeded by the first element, like `scanl1` of Haskell,
+/// i.e. x0, f(x0, x1), f(f(x0, x1), x2)... and nothing for empty input
+///
+/// # Arguments
+/// * `f`: f :: (T, T) -> T
+/// * `it`: [`IntoIterator`] T, T must impl [`Clone`]
+pub fn scanl1<T: Clone>(f: impl Fn(T, T) -> T, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    let mut acc: Option<T> = None;
+    it.into_iter().map(move |x| {
+        let next = match acc.take() {
+            Some(prev) => f(prev, x),
+            None => x
+        };
+        acc = Some(next.clone());
+        next
+    })
+}
+
+/// Lazily yield at most n elements of each distinct key in input order, the rest are dropped
+///
+/// # Arguments
+/// * `n`: [`usize`], quota of each key
+/// * `key`: key :: &T -> K, K must impl [`Hash`] and [`Eq`]
+/// * `it`: [`IntoIterator`] T
+pub fn take_per_key<T, K: Hash + Eq>(n: usize, key: impl Fn(&T) -> K, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    let mut taken: HashMap<K, usize> = HashMap::new();
+    it.into_iter().filter(move |x| {
+        let count = taken.entry(key(x)).or_insert(0);
+        *count += 1;
+        *count <= n
+    })
+}
+
+/// Greedily pack elements into chunks whose total weight stays under max_weight,
+/// an element heavier than max_weight gets a chunk of its own
+///
+/// # Arguments
+/// * `max_weight`: W, W must impl [`Add`], [`PartialOrd`] and [`Copy`]
+/// * `weight`: weight :: &T -> W
+/// * `it`: [`IntoIterator`] T
+pub fn chunks_weighted<T, W>(max_weight: W, weight: impl Fn(&T) -> W, it: impl IntoIterator<Item=T>) -> Vec<Vec<T>>
+    where W: Copy + PartialOrd + Add<Output=W>
+{
+    let mut ret: Vec<Vec<T>> = Vec::new();
+    let mut total: Option<W> = None;
+    for x in it {
+        let w = weight(&x);
+        match total {
+            Some(t) if t + w <= max_weight => {
+                total = Some(t + w);
+                ret.last_mut().unwrap().push(x);
+            }
+            _ => {
+                total = Some(w);
+                ret.push(vec![x]);
+            }
+        }
+    }
+    ret
+}
+
+/// Lazily cut into chunks, a new chunk starts whenever the running total of value would exceed limit,
+/// an element over limit gets a chunk of its own. Lazy version of [chunks_weighted](basic_fn::fun::chunks_weighted)
+/// which works on long or infinite streams
+///
+/// # Arguments
+/// * `limit`: W, W must impl [`Add`], [`PartialOrd`] and [`Copy`]
+/// * `value`: value :: &T -> W
+/// * `it`: [`IntoIterator`] T
+pub fn chunk_by_running_total<T, W>(limit: W, value: impl Fn(&T) -> W, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=Vec<T>>
+    where W: Copy + PartialOrd + Add<Output=W>
+{
+    let mut it = it.into_iter().peekable();
+    from_fn(move || {
+        let x = it.next()?;
+        let mut total = value(&x);
+        let mut chunk = vec![x];
+        while let Some(y) = it.next_if(|y| total + value(y) <= limit) {
+            total = total + value(&y);
+            chunk.push(y);
+        }
+        Some(chunk)
+    })
+}
+
+/// Count maximal runs of consecutive equal elements without building the groups
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T, T must impl [`PartialEq`]
+pub fn count_groups<T: PartialEq>(it: impl IntoIterator<Item=T>) -> usize {
+    run_lengths(it).count()
+}
+
+/// Count maximal runs of consecutive elements of same key without building the groups
+///
+/// # Arguments
+/// * `key`: key :: &T -> K, K must impl [`PartialEq`]
+/// * `it`: [`IntoIterator`] T
+pub fn count_groups_by<T, K: PartialEq>(key: impl Fn(&T) -> K, it: impl IntoIterator<Item=T>) -> usize {
+    count_groups(it.into_iter().map(|x| key(&x)))
+}
+
+/// Combine pairs of two [`IntoIterator`]s by fallible f, stop at the shorter one,
+/// short-circuit and return the first [`Err`]
+///
+/// # Arguments
+/// * `f`: f :: (A, B) -> [`Result`] C E
+/// * `a`: [`IntoIterator`] A
+/// * `b`: [`IntoIterator`] B
+pub fn zip_try_with<A,B,C,E>(f: impl Fn(A, B) -> Result<C, E>, a: impl IntoIterator<Item=A>, b: impl IntoIterator<Item=B>) -> Result<Vec<C>, E> {
+    zip_map(f, a, b).collect()
+}
+
+/// Lazily process inputs threading a state, one output per input,
+/// lazy version of [mapaccum](basic_fn::fun::mapaccum) for stateful stream processing
+///
+/// # Arguments
+/// * `seed`: S, initial state
+/// * `f`: f :: (S, T) -> (S, U), return the next state and the output
+/// * `inputs`: [`IntoIterator`] T
+pub fn feedback<S,T,U>(seed: S, f: impl Fn(S, T) -> (S, U), inputs: impl IntoIterator<Item=T>) -> impl Iterator<Item=U> {
+    let mut state = Some(seed);
+    inputs.into_iter().map(move |x| {
+        let (next, y) = f(state.take().unwrap(), x);
+        state = Some(next);
+        y
+    })
+}
+
+/// Lazily keep elements by both index and value, without zipping with `enumerate` first
+///
+/// # Arguments
+/// * `pred`: pred :: ([`usize`], &T) -> [`bool`]
+/// * `it`: [`IntoIterator`] T
+pub fn filter_indexed<T>(pred: impl Fn(usize, &T) -> bool, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    it.into_iter().enumerate().filter(move |(i, x)| pred(*i, x)).map(|(_, x)| x)
+}
+
+/// Lazily keep about ratio of elements by deterministic even spacing, e.g. for downsampling a plot.
+/// Element at index i is kept iff floor((i + 1) * ratio) > floor(i * ratio), i.e. an accumulator
+/// increased by ratio per element keeps one element each time it crosses an integer, so exactly
+/// floor(m * ratio) of the first m elements are kept
+///
+/// # Arguments
+/// * `ratio`: [`f64`], in 0.0..=1.0
+/// * `it`: [`IntoIterator`] T
+///
+/// # Panics
+/// if ratio is not in 0.0..=1.0
+pub fn sample_ratio<T>(ratio: f64, it: impl IntoIterator<Item=T>) -> impl Iterator<Item=T> {
+    assert!((0.0..=1.0).contains(&ratio), "sample_ratio requires a ratio in 0.0..=1.0");
+    filter_indexed(move |i, _| ((i + 1) as f64 * ratio).floor() > (i as f64 * ratio).floor(), it)
+}
+
+/// Duplicate a lazy iterator into two independent ones both yielding the whole sequence,
+/// like `itertools.tee` of Python. Elements pulled by the leading side are cloned into a buffer
+/// until the other side consumes them, so memory grows with the distance between the two sides,
+/// consuming one side fully before the other buffers the whole input.
+/// Once one side is dropped nothing is buffered any more, the other side just pulls the source.
+/// Both sides share the source by [`Rc`] so they are not [`Send`]
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T, T must impl [`Clone`]
+pub fn tee<T: Clone>(it: impl IntoIterator<Item=T>) -> (impl Iterator<Item=T>, impl Iterator<Item=T>) {
+    let shared = Rc::new(RefCell::new(TeeBuffer { it: it.into_iter().fuse(), backlog: VecDeque::new(), lagging: false, dropped: false }));
+    (TeeSide { shared: shared.clone(), side: false }, TeeSide { shared, side: true })
+}
+
+struct TeeBuffer<I: Iterator> {
+    // fused so that both sides agree once the source is exhausted
+    it: Fuse<I>,
+    // elements not yet consumed by the lagging side
+    backlog: VecDeque<I::Item>,
+    lagging: bool,
+    // one side is dropped, so the other one never needs a backlog
+    dropped: bool,
+}
+
+struct TeeSide<I: Iterator> {
+    shared: Rc<RefCell<TeeBuffer<I>>>,
+    side: bool,
+}
+
+impl<I: Iterator> Iterator for TeeSide<I> where I::Item: Clone {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let mut buf = self.shared.borrow_mut();
+        if buf.lagging == self.side {
+            if let Some(x) = buf.backlog.pop_front() {
+                return Some(x);
+            }
+        }
+        let x = buf.it.next()?;
+        if !buf.dropped {
+            buf.backlog.push_back(x.clone());
+            buf.lagging = !self.side;
+        }
+        Some(x)
+    }
+}
+
+impl<I: Iterator> Drop for TeeSide<I> {
+    fn drop(&mut self) {
+        let mut buf = self.shared.borrow_mut();
+        buf.dropped = true;
+        // backlog of the dropped side will never be consumed
+        if buf.lagging == self.side {
+            buf.backlog.clear();
+        }
+    }
 }
\ No newline at end of file
@@ -140,6 +140,15 @@
     };
 }
 
+/// Curry macro of [filter_indexed](basic_fn::fun::filter_indexed)
+/// 
+/// **Signature**: filter_indexed :: ((usize, T) -> bool) -> [`Iterator`] T -> [`Iterator`] T
+#[macro_export] macro_rules! filter_indexed {
+    ($f:expr) => {
+        move |it| filter_indexed($f, it)
+    };
+}
+
 /// This macro is used to provide shortcut of function composition.
 /// The order is last-in-first-invoke.
 /// 
@@ -200,7 +209,7 @@
 #[macro_export] macro_rules! concat {
     ($($it:expr);*) => {{
         let mut ret = Vec::new();
-        $(ret.extend($it););*
+        $(ret.extend($it);)*
         ret
     }};
 }
@@ -360,13 +369,13 @@
     ($f:expr) => {move |it| sorted_by($f, it)};
 }
 
-/// macro of sorted_with
-/// 
-/// fs = [f], f :: (&T, &T) -> Ordering
-/// 
-/// Syntax:
-/// 1. sorted_with!(fs,...) :: [`Iterator`] T -> [`Iterator`] T
-/// 2. sorted_with!(fs,...;it) = [`Iterator`] T
+// macro of sorted_with
+// 
+// fs = [f], f :: (&T, &T) -> Ordering
+// 
+// Syntax:
+// 1. sorted_with!(fs,...) :: [`Iterator`] T -> [`Iterator`] T
+// 2. sorted_with!(fs,...;it) = [`Iterator`] T
 // TODO - to be continued
 // #[macro_export] macro_rules! sorted_with {
 //     ($($f:expr),*) => {move |it| sorted_with!($($f),*;it)};
@@ -401,4 +410,124 @@
     ($f:expr) => {move |it1, it2| zip_with($f, it1, it2)};
     ($f:expr=>) => {move |it1| (move |it2| zip_with($f, it1, it2))};
     ($f:expr,$it:expr) => {move |it| zip_with($f, $it, it)};
+}
+
+/// macro of [cons](basic_fn::fun::cons)
+///
+/// Syntax:
+/// 1. cons!(x) :: [`Iterator`] T -> [`Iterator`] T
+/// 2. cons!(x, it) = cons(x, it)
+#[macro_export] macro_rules! cons {
+    ($x:expr) => {move |it| cons($x, it)};
+    ($x:expr,$it:expr) => {{cons($x, $it)}};
+}
+
+/// macro of [apply](basic_fn::fun::apply) and [apply_to](basic_fn::fun::apply_to)
+///
+/// Syntax:
+/// 1. apply!(f) :: T -> R
+/// 2. apply!(_, x) :: (T -> R) -> R
+/// 3. apply!(f, x) = f(x)
+#[macro_export] macro_rules! apply {
+    (_,$x:expr) => {apply_to($x)};
+    ($f:expr) => {move |x| apply(&$f, x)};
+    ($f:expr,$x:expr) => {{apply($f, $x)}};
+}
+
+/// This macro is used to thread a value through functions immediately.
+/// The order is same as `pipe!`, but the result is evaluated instead of returning a closure.
+///
+/// Syntax:
+/// thread!(x, f1, f2, ...) = f2(f1(x))...
+#[macro_export] macro_rules! thread {
+    ($v:expr) => {{$v}};
+    ($v:expr,$($f:expr),*) => {{
+        pipe!(@NEXT $v, $($f),*)
+    }};
+}
+
+/// This macro is used to thread a value through stages with named intermediate results.
+/// Each binding is in scope for all later stages, the last stage is the result.
+///
+/// Syntax:
+/// let_pipe!(x = 3; y = x + 1; y * 2) = 8
+#[macro_export] macro_rules! let_pipe {
+    ($name:ident = $e:expr; $($rest:tt)+) => {{
+        let $name = $e;
+        let_pipe!($($rest)+)
+    }};
+    ($e:expr) => {{$e}};
+}
+
+/// Construct [`Option`] with condition, value is only evaluated when condition holds
+///
+/// Syntax:
+/// 1. maybe!(cond, x) = if cond { Some(x) } else { None }
+/// 2. maybe!(x) = Some(x)
+#[macro_export] macro_rules! maybe {
+    ($cond:expr,$x:expr) => {{
+        if $cond { Some($x) } else { None }
+    }};
+    ($x:expr) => {{Some($x)}};
+}
+
+/// Boxed version of `compose!`, so that the composition has a nameable type
+/// and can be stored in struct field or returned from function.
+/// The order is last-in-first-invoke.
+///
+/// Syntax: compose_boxed!(f1, f2, ...) :: Box<dyn Fn(T) -> U>
+#[macro_export] macro_rules! compose_boxed {
+    ($($f:expr),*) => {{
+        let f: Box<dyn Fn(_) -> _ + '_> = Box::new(compose!($($f),*));
+        f
+    }};
+}
+
+/// Print value with file and line to stderr and return it unchanged, like `dbg!` but fit in `pipe!`
+///
+/// Syntax:
+/// 1. dbg_tap!() :: [`Debug`](std::fmt::Debug) T => T -> T
+/// 2. dbg_tap!(x) = x
+#[macro_export] macro_rules! dbg_tap {
+    () => {move |x| {
+        eprintln!("[{}:{}] {:?}", file!(), line!(), &x);
+        x
+    }};
+    ($x:expr) => {{
+        match $x {
+            tmp => {
+                eprintln!("[{}:{}] {} = {:?}", file!(), line!(), stringify!($x), &tmp);
+                tmp
+            }
+        }
+    }};
+}
+
+/// This macro is used to provide shortcut of function pipeline which may stop early.
+/// The order is last-in-last-invoke like `pipe!`, result of each stage is checked by pred,
+/// once it fails the value before that stage is returned and the remaining stages are skipped.
+///
+/// To be able to return it, the value is cloned before every stage, so T must impl [`Clone`]
+/// and the clone costs once per stage
+///
+/// Syntax: pipe_while!(pred; f1, f2, ...) :: [`Clone`] T => T -> T, pred :: &T -> [`bool`]
+#[macro_export] macro_rules! pipe_while {
+    ($p:expr; $($f:expr),+) => {
+        move |v| {
+            let p = $p;
+            let cur = Ok(v);
+            $(
+                let cur = match cur {
+                    Ok(prev) => {
+                        let next = $f(::std::clone::Clone::clone(&prev));
+                        if p(&next) { Ok(next) } else { Err(prev) }
+                    }
+                    stopped => stopped
+                };
+            )+
+            match cur {
+                Ok(v) | Err(v) => v
+            }
+        }
+    };
 }
\ No newline at end of file
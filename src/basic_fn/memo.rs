@@ -0,0 +1,168 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Module memo provide memoization of pure functions, results are cached by the arguments.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Storage of memoized results, decoupling the caching policy from [memoize_with](basic_fn::memo::memoize_with)
+pub trait Cache<K, V> {
+    /// cached value of key, [`None`] if absent or evicted
+    fn get(&mut self, key: &K) -> Option<&V>;
+    /// store value of key, the cache may evict other entries
+    fn insert(&mut self, key: K, value: V);
+}
+
+impl<K: Hash + Eq, V> Cache<K, V> for HashMap<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        HashMap::get(self, key)
+    }
+    fn insert(&mut self, key: K, value: V) {
+        HashMap::insert(self, key, value);
+    }
+}
+
+impl<K: Ord, V> Cache<K, V> for BTreeMap<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        BTreeMap::get(self, key)
+    }
+    fn insert(&mut self, key: K, value: V) {
+        BTreeMap::insert(self, key, value);
+    }
+}
+
+/// Cache keeping at most capacity entries, the least recently used one is evicted first.
+/// Both get and insert take O(log capacity)
+#[derive(Debug, Clone)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    tick: u64,
+    // value and last used tick of key
+    entries: HashMap<K, (V, u64)>,
+    // key of last used tick, the first one is the least recently used
+    order: BTreeMap<u64, K>,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// Empty cache of capacity
+    ///
+    /// # Arguments
+    /// * `capacity`: [`usize`], max count of entries
+    ///
+    /// # Panics
+    /// if capacity == 0
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "LruCache requires a capacity greater than 0");
+        LruCache { capacity, tick: 0, entries: HashMap::new(), order: BTreeMap::new() }
+    }
+
+    /// Count of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn touch(&mut self, key: &K) -> u64 {
+        self.tick += 1;
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.order.remove(&entry.1);
+            entry.1 = self.tick;
+        }
+        self.order.insert(self.tick, key.clone());
+        self.tick
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Cache<K, V> for LruCache<K, V> {
+    fn get(&mut self, key: &K) -> Option<&V> {
+        if !self.entries.contains_key(key) {
+            return None;
+        }
+        self.touch(key);
+        self.entries.get(key).map(|entry| &entry.0)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if !self.entries.contains_key(&key) && self.entries.len() == self.capacity {
+            if let Some((_, oldest)) = self.order.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        let tick = self.touch(&key);
+        self.entries.insert(key, (value, tick));
+    }
+}
+
+/// Memoize a function of one argument, f is called at most once for each distinct argument
+///
+/// # Arguments
+/// * `f`: f :: A -> R, A must impl [`Hash`], [`Eq`] and [`Clone`], R must impl [`Clone`]
+pub fn memoize<A, R>(f: impl Fn(A) -> R) -> impl FnMut(A) -> R
+    where A: Hash + Eq + Clone, R: Clone
+{
+    memoize_with(HashMap::new(), f)
+}
+
+/// Memoize a function of one argument with a custom cache, f is called again for arguments
+/// which are evicted by the cache
+///
+/// # Arguments
+/// * `cache`: C, C must impl [`Cache`] A R, e.g. [`HashMap`], [`BTreeMap`] or [`LruCache`]
+/// * `f`: f :: A -> R, A must impl [`Clone`], R must impl [`Clone`]
+pub fn memoize_with<A, R, C>(mut cache: C, f: impl Fn(A) -> R) -> impl FnMut(A) -> R
+    where A: Clone, R: Clone, C: Cache<A, R>
+{
+    move |a| {
+        if let Some(r) = cache.get(&a) {
+            return r.clone();
+        }
+        let r = f(a.clone());
+        cache.insert(a, r.clone());
+        r
+    }
+}
+
+/// Memoize a function of two arguments, cached by the pair (a, b), see [memoize](basic_fn::memo::memoize).
+/// The returned function can not be called from f, use [memoize2_rec](basic_fn::memo::memoize2_rec) for recurrences
+///
+/// # Arguments
+/// * `f`: f :: (A, B) -> R, A and B must impl [`Hash`], [`Eq`] and [`Clone`], R must impl [`Clone`]
+pub fn memoize2<A, B, R>(f: impl Fn(A, B) -> R) -> impl FnMut(A, B) -> R
+    where A: Hash + Eq + Clone, B: Hash + Eq + Clone, R: Clone
+{
+    let mut memo = memoize(move |(a, b)| f(a, b));
+    move |a, b| memo((a, b))
+}
+
+/// Memoize a recursive function of two arguments, like edit distance. f receives the memoized
+/// function itself as first argument for recursive calls, so each distinct pair (a, b) is computed once
+///
+/// # Arguments
+/// * `f`: f :: (&mut FnMut(A, B) -> R, A, B) -> R, A and B must impl [`Hash`], [`Eq`] and [`Clone`], R must impl [`Clone`]
+pub fn memoize2_rec<A, B, R, F>(f: F) -> impl FnMut(A, B) -> R
+    where A: Hash + Eq + Clone, B: Hash + Eq + Clone, R: Clone, F: Fn(&mut dyn FnMut(A, B) -> R, A, B) -> R
+{
+    let mut cache: HashMap<(A, B), R> = HashMap::new();
+    move |a, b| call_rec(&f, &mut cache, a, b)
+}
+
+fn call_rec<A, B, R, F>(f: &F, cache: &mut HashMap<(A, B), R>, a: A, b: B) -> R
+    where A: Hash + Eq + Clone, B: Hash + Eq + Clone, R: Clone, F: Fn(&mut dyn FnMut(A, B) -> R, A, B) -> R
+{
+    let key = (a, b);
+    if let Some(r) = cache.get(&key) {
+        return r.clone();
+    }
+    let r = f(&mut |x, y| call_rec(f, cache, x, y), key.0.clone(), key.1.clone());
+    cache.insert(key, r.clone());
+    r
+}
\ No newline at end of file
@@ -10,4 +10,12 @@
 
 pub mod fun;
 #[macro_use]
-pub mod mac;
\ No newline at end of file
+pub mod mac;
+pub mod control;
+pub mod strings;
+pub mod rec;
+pub mod transducer;
+pub mod memo;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod diff;
\ No newline at end of file
@@ -0,0 +1,47 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Module random provide randomized functions, only available with feature `rand`.
+//! The random source is always passed by caller so that results are reproducible with a seeded [`rand::Rng`].
+
+use rand::Rng;
+
+/// Uniformly sample up to k elements in one pass over input of unknown length (Algorithm R),
+/// only k elements are kept in memory
+///
+/// # Arguments
+/// * `k`: [`usize`], size of sample
+/// * `it`: [`IntoIterator`] T
+/// * `rng`: &mut R, R must impl [`Rng`]
+pub fn reservoir_sample<T, R: Rng + ?Sized>(k: usize, it: impl IntoIterator<Item=T>, rng: &mut R) -> Vec<T> {
+    let mut ret = Vec::with_capacity(k);
+    for (i, x) in it.into_iter().enumerate() {
+        if i < k {
+            ret.push(x);
+        } else {
+            let j = rng.gen_range(0..=i);
+            if j < k {
+                ret[j] = x;
+            }
+        }
+    }
+    ret
+}
+
+/// Collect into a new [`Vec`] in uniformly random order (Fisher-Yates), input is left untouched
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T
+/// * `rng`: &mut R, R must impl [`Rng`], pass a seeded one (e.g. `StdRng::seed_from_u64`) for reproducible result
+pub fn shuffle<T, R: Rng + ?Sized>(it: impl IntoIterator<Item=T>, rng: &mut R) -> Vec<T> {
+    let mut ret: Vec<T> = it.into_iter().collect();
+    for i in (1..ret.len()).rev() {
+        let j = rng.gen_range(0..=i);
+        ret.swap(i, j);
+    }
+    ret
+}
\ No newline at end of file
@@ -0,0 +1,45 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Module rec provide functions for recursion and iteration schemes.
+
+/// Apply function repeatedly from `x0` until two successive values are equal, return the converged value
+///
+/// Never returns if the iteration does not converge, see [fixpoint_within](basic_fn::rec::fixpoint_within)
+///
+/// # Arguments
+/// * `f`: f :: T -> T
+/// * `x0`: T, initial value, T must impl [`PartialEq`] and [`Clone`]
+pub fn fixpoint<T: PartialEq + Clone>(f: impl Fn(T) -> T, x0: T) -> T {
+    let mut x = x0;
+    loop {
+        let next = f(x.clone());
+        if next == x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+/// Apply function repeatedly from `x0` until two successive values are equal, at most `max_iters` times.
+/// Return [`None`] if it does not converge in time.
+///
+/// # Arguments
+/// * `max_iters`: [`usize`], max count of invoking f
+/// * `f`: f :: T -> T
+/// * `x0`: T, initial value, T must impl [`PartialEq`] and [`Clone`]
+pub fn fixpoint_within<T: PartialEq + Clone>(max_iters: usize, f: impl Fn(T) -> T, x0: T) -> Option<T> {
+    let mut x = x0;
+    for _ in 0..max_iters {
+        let next = f(x.clone());
+        if next == x {
+            return Some(x);
+        }
+        x = next;
+    }
+    None
+}
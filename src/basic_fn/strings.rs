@@ -0,0 +1,122 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Module strings provide short-hand functions for [`String`] and [`str`] operations.
+
+use std::fmt::Display;
+use std::str::{Bytes, Chars};
+
+/// Collect [`IntoIterator`]<T> into a [`String`], T can be [`char`], &[`str`] or [`String`].
+/// Capacity is preallocated by the lower bound of size hint.
+///
+/// # Arguments
+/// * `it`: [`IntoIterator`] T
+pub fn collect_string<T>(it: impl IntoIterator<Item=T>) -> String
+    where String: Extend<T>
+{
+    let it = it.into_iter();
+    let mut ret = String::with_capacity(it.size_hint().0);
+    ret.extend(it);
+    ret
+}
+
+/// Get [`Iterator`] of [`char`] of a [`str`]
+///
+/// # Arguments
+/// * `s`: &[`str`]
+pub fn chars(s: &str) -> Chars<'_> {
+    s.chars()
+}
+
+/// Get [`Iterator`] of [`u8`] of a [`str`]
+///
+/// # Arguments
+/// * `s`: &[`str`]
+pub fn bytes(s: &str) -> Bytes<'_> {
+    s.bytes()
+}
+
+/// Get upper case of a [`str`] as new [`String`], Unicode-aware, e.g. "ß" -> "SS"
+///
+/// # Arguments
+/// * `s`: &[`str`]
+pub fn to_upper(s: &str) -> String {
+    s.to_uppercase()
+}
+
+/// Get lower case of a [`str`] as new [`String`], Unicode-aware
+///
+/// # Arguments
+/// * `s`: &[`str`]
+pub fn to_lower(s: &str) -> String {
+    s.to_lowercase()
+}
+
+/// Reverse a [`str`] by [`char`] as new [`String`]
+///
+/// Note that this reverses Unicode scalar values rather than grapheme clusters,
+/// so combining marks will be detached from their base character, e.g. "e\u{301}" -> "\u{301}e".
+///
+/// # Arguments
+/// * `s`: &[`str`]
+pub fn reverse_str(s: &str) -> String {
+    s.chars().rev().collect()
+}
+
+/// Remove leading and trailing whitespace of a [`str`]
+///
+/// # Arguments
+/// * `s`: &[`str`]
+pub fn trim(s: &str) -> &str {
+    s.trim()
+}
+
+/// Remove leading whitespace of a [`str`]
+///
+/// # Arguments
+/// * `s`: &[`str`]
+pub fn trim_start(s: &str) -> &str {
+    s.trim_start()
+}
+
+/// Remove trailing whitespace of a [`str`]
+///
+/// # Arguments
+/// * `s`: &[`str`]
+pub fn trim_end(s: &str) -> &str {
+    s.trim_end()
+}
+
+/// Map each whitespace-delimited word of a [`str`] and rejoin them with single space
+///
+/// # Arguments
+/// * `f`: f :: &[`str`] -> [`String`]
+/// * `s`: &[`str`]
+pub fn map_words(f: impl Fn(&str) -> String, s: &str) -> String {
+    s.split_whitespace().map(f).collect::<Vec<_>>().join(" ")
+}
+
+/// Join items into a [`String`] with separator, `last_sep` is used before the final item,
+/// e.g. "a, b and c"
+///
+/// # Arguments
+/// * `sep`: &[`str`], separator between items
+/// * `last_sep`: &[`str`], separator before the final item
+/// * `it`: [`IntoIterator`] T, T must impl [`Display`]
+pub fn punctuate<T: Display>(sep: &str, last_sep: &str, it: impl IntoIterator<Item=T>) -> String {
+    let items = it.into_iter().map(|x| x.to_string()).collect::<Vec<_>>();
+    let mut ret = String::new();
+    for (i, item) in items.iter().enumerate() {
+        if i + 1 == items.len() && i > 0 {
+            ret.push_str(last_sep);
+        } else if i > 0 {
+            ret.push_str(sep);
+        }
+        ret.push_str(item);
+    }
+    ret
+}
\ No newline at end of file
@@ -0,0 +1,147 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Module transducer provide composable transformations which are independent of both the source
+//! and the reduction, so that a pipeline is built once and applied to many inputs
+//! without intermediate collections.
+
+/// Result of one reducing step, `Done` stops the reduction early
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step<A> {
+    Continue(A),
+    Done(A),
+}
+
+/// A transformation of reducing functions, from reducer of `Output` to reducer of `T`
+pub trait Transducer<T> {
+    /// item type passed to the downstream reducer
+    type Output;
+
+    /// Wrap a downstream reducer into a reducer of T, state of the transducer (e.g. count of take)
+    /// lives in the returned reducer, so each application starts from a fresh state
+    fn transform<A, R>(&self, reducer: R) -> impl FnMut(A, T) -> Step<A>
+        where R: FnMut(A, Self::Output) -> Step<A>;
+
+    /// Compose with next transducer, items flow through self first
+    fn then<X: Transducer<Self::Output>>(self, next: X) -> Compose<Self, X>
+        where Self: Sized
+    {
+        Compose(self, next)
+    }
+}
+
+/// Transducer of mapping, see [mapping](basic_fn::transducer::mapping)
+#[derive(Debug, Clone, Copy)]
+pub struct Map<F>(F);
+
+/// Transducer of filtering, see [filtering](basic_fn::transducer::filtering)
+#[derive(Debug, Clone, Copy)]
+pub struct Filter<P>(P);
+
+/// Transducer of taking, see [taking](basic_fn::transducer::taking)
+#[derive(Debug, Clone, Copy)]
+pub struct Take(usize);
+
+/// Composition of two transducers, see [then](basic_fn::transducer::Transducer::then)
+#[derive(Debug, Clone, Copy)]
+pub struct Compose<X, Y>(X, Y);
+
+/// Transducer mapping each item by f
+///
+/// # Arguments
+/// * `f`: f :: T -> U
+pub fn mapping<T, U, F: Fn(T) -> U>(f: F) -> Map<F> {
+    Map(f)
+}
+
+/// Transducer keeping items satisfying predicate
+///
+/// # Arguments
+/// * `p`: p :: &T -> [`bool`]
+pub fn filtering<T, P: Fn(&T) -> bool>(p: P) -> Filter<P> {
+    Filter(p)
+}
+
+/// Transducer taking first n items and stopping the reduction after that
+///
+/// # Arguments
+/// * `n`: [`usize`], count of items to take
+pub fn taking(n: usize) -> Take {
+    Take(n)
+}
+
+impl<T, U, F: Fn(T) -> U> Transducer<T> for Map<F> {
+    type Output = U;
+
+    fn transform<A, R>(&self, mut reducer: R) -> impl FnMut(A, T) -> Step<A>
+        where R: FnMut(A, U) -> Step<A>
+    {
+        let f = &self.0;
+        move |acc, x| reducer(acc, f(x))
+    }
+}
+
+impl<T, P: Fn(&T) -> bool> Transducer<T> for Filter<P> {
+    type Output = T;
+
+    fn transform<A, R>(&self, mut reducer: R) -> impl FnMut(A, T) -> Step<A>
+        where R: FnMut(A, T) -> Step<A>
+    {
+        let p = &self.0;
+        move |acc, x| if p(&x) { reducer(acc, x) } else { Step::Continue(acc) }
+    }
+}
+
+impl<T> Transducer<T> for Take {
+    type Output = T;
+
+    fn transform<A, R>(&self, mut reducer: R) -> impl FnMut(A, T) -> Step<A>
+        where R: FnMut(A, T) -> Step<A>
+    {
+        let mut left = self.0;
+        move |acc, x| {
+            if left == 0 {
+                return Step::Done(acc);
+            }
+            left -= 1;
+            match reducer(acc, x) {
+                Step::Continue(acc) if left == 0 => Step::Done(acc),
+                step => step
+            }
+        }
+    }
+}
+
+impl<T, X: Transducer<T>, Y: Transducer<X::Output>> Transducer<T> for Compose<X, Y> {
+    type Output = Y::Output;
+
+    fn transform<A, R>(&self, reducer: R) -> impl FnMut(A, T) -> Step<A>
+        where R: FnMut(A, Y::Output) -> Step<A>
+    {
+        self.0.transform(self.1.transform(reducer))
+    }
+}
+
+/// Reduce [`IntoIterator`]<T> from `init` by `reducer`, with items transformed by transducer `xf`.
+/// No intermediate collection or iterator is built, and the transducer can be reused for other inputs.
+///
+/// # Arguments
+/// * `xf`: &X, X must impl [`Transducer`]<T>
+/// * `reducer`: reducer :: (A, X::Output) -> A
+/// * `init`: A, initial value of reduction
+/// * `it`: [`IntoIterator`] T
+pub fn transduce<T, A, X: Transducer<T>>(xf: &X, mut reducer: impl FnMut(A, X::Output) -> A, init: A, it: impl IntoIterator<Item=T>) -> A {
+    let mut step = xf.transform(move |acc, x| Step::Continue(reducer(acc, x)));
+    let mut acc = init;
+    for x in it {
+        match step(acc, x) {
+            Step::Continue(next) => acc = next,
+            Step::Done(ret) => return ret
+        }
+    }
+    acc
+}
@@ -0,0 +1,258 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Persistent singly linked list (cons list), nodes are shared by [`Rc`] so that
+//! [cons](List::cons) and [tail](List::tail) are O(1) without copying.
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::rc::Rc;
+
+/// Immutable cons list
+pub struct List<T>(Option<Rc<Node<T>>>);
+
+struct Node<T> {
+    head: T,
+    tail: List<T>,
+}
+
+impl<T> List<T> {
+    /// Empty list
+    pub fn new() -> Self {
+        List(None)
+    }
+
+    /// New list with x in front of self, self is shared instead of copied
+    ///
+    /// # Arguments
+    /// * `x`: T
+    pub fn cons(&self, x: T) -> Self {
+        List(Some(Rc::new(Node { head: x, tail: self.clone() })))
+    }
+
+    /// First element, [`None`] if empty
+    pub fn head(&self) -> Option<&T> {
+        self.0.as_ref().map(|node| &node.head)
+    }
+
+    /// List without the first element sharing the nodes of self, [`None`] if empty
+    pub fn tail(&self) -> Option<Self> {
+        self.0.as_ref().map(|node| node.tail.clone())
+    }
+
+    /// Whether the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Count of elements, O(n)
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Whether two lists are the same nodes, i.e. structure is shared
+    ///
+    /// # Arguments
+    /// * `other`: &List T
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false
+        }
+    }
+
+    /// Iterate over references of elements from the head
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.0.as_deref())
+    }
+
+    /// New list of f applied on each element
+    ///
+    /// # Arguments
+    /// * `f`: f :: &T -> U
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> List<U> {
+        self.iter().map(f).collect()
+    }
+
+    /// New list of elements satisfying predicate
+    ///
+    /// # Arguments
+    /// * `p`: p :: &T -> [`bool`]
+    pub fn filter(&self, p: impl Fn(&T) -> bool) -> Self where T: Clone {
+        self.iter().filter(|x| p(x)).cloned().collect()
+    }
+
+    /// Fold from the end, f(x0, f(x1, ... f(xn, init))), without recursion
+    ///
+    /// # Arguments
+    /// * `init`: R
+    /// * `f`: f :: (&T, R) -> R
+    pub fn foldr<R>(&self, init: R, f: impl Fn(&T, R) -> R) -> R {
+        self.iter().collect::<Vec<_>>().into_iter().rev().fold(init, |acc, x| f(x, acc))
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
+impl<T> Clone for List<T> {
+    fn clone(&self) -> Self {
+        List(self.0.clone())
+    }
+}
+
+impl<T> Drop for List<T> {
+    // drop uniquely owned nodes in a loop, the default recursive drop overflows the stack on long lists
+    fn drop(&mut self) {
+        let mut cur = self.0.take();
+        while let Some(node) = cur {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => cur = node.tail.0.take(),
+                Err(_) => break
+            }
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item=T>>(it: I) -> Self {
+        let items: Vec<T> = it.into_iter().collect();
+        items.into_iter().rev().fold(List::new(), |acc, x| acc.cons(x))
+    }
+}
+
+/// Borrowing iterator of [`List`]
+pub struct Iter<'a, T>(Option<&'a Node<T>>);
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.0?;
+        self.0 = node.tail.0.as_deref();
+        Some(&node.head)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Owning iterator of [`List`], elements of shared nodes are cloned
+pub struct IntoIter<T>(List<T>);
+
+impl<T: Clone> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = (self.0).0.take()?;
+        match Rc::try_unwrap(node) {
+            Ok(Node { head, tail }) => {
+                self.0 = tail;
+                Some(head)
+            }
+            Err(node) => {
+                self.0 = node.tail.clone();
+                Some(node.head.clone())
+            }
+        }
+    }
+}
+
+impl<T: Clone> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<T: Clone> List<T> {
+    /// Zipper focusing on the head
+    pub fn zipper(&self) -> Zipper<T> {
+        Zipper { left: List::new(), right: self.clone() }
+    }
+}
+
+/// Cursor over [`List`] with O(1) focus movement and edit at focus.
+/// The focus may also be at the end (after the last element) where it is [`None`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Zipper<T> {
+    // elements before focus, nearest first
+    left: List<T>,
+    // focus followed by elements after it
+    right: List<T>,
+}
+
+impl<T: Clone> Zipper<T> {
+    /// Focused element, [`None`] at the end
+    pub fn focus(&self) -> Option<&T> {
+        self.right.head()
+    }
+
+    /// Move focus one step left, [`None`] at the beginning
+    pub fn left(&self) -> Option<Self> {
+        let x = self.left.head()?.clone();
+        Some(Zipper { left: self.left.tail()?, right: self.right.cons(x) })
+    }
+
+    /// Move focus one step right, [`None`] at the end
+    pub fn right(&self) -> Option<Self> {
+        let x = self.right.head()?.clone();
+        Some(Zipper { left: self.left.cons(x), right: self.right.tail()? })
+    }
+
+    /// Replace focused element by x, [`None`] at the end
+    ///
+    /// # Arguments
+    /// * `x`: T
+    pub fn set(&self, x: T) -> Option<Self> {
+        Some(Zipper { left: self.left.clone(), right: self.right.tail()?.cons(x) })
+    }
+
+    /// Insert x before the focused element, x becomes the focus
+    ///
+    /// # Arguments
+    /// * `x`: T
+    pub fn insert(&self, x: T) -> Self {
+        Zipper { left: self.left.clone(), right: self.right.cons(x) }
+    }
+
+    /// Remove focused element, the next element becomes the focus, [`None`] at the end
+    pub fn delete(&self) -> Option<Self> {
+        Some(Zipper { left: self.left.clone(), right: self.right.tail()? })
+    }
+
+    /// Rebuild the list, elements after focus are shared, O(count of elements before focus)
+    pub fn to_list(&self) -> List<T> {
+        self.left.iter().fold(self.right.clone(), |acc, x| acc.cons(x.clone()))
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Persistent vector, a bit-partitioned trie of branching factor 32. [push](PVec::push) and
+//! [update](PVec::update) copy only the path from root to one leaf, O(log32 n), and share the rest
+//! with the previous version.
+
+use std::iter::{from_fn, FromIterator};
+use std::rc::Rc;
+
+const BITS: u32 = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+enum Node<T> {
+    Branch(Vec<Rc<Node<T>>>),
+    Leaf(Vec<T>),
+}
+
+/// Immutable vector, every modification returns a new version
+pub struct PVec<T> {
+    root: Rc<Node<T>>,
+    len: usize,
+    // level of root, 0 means root is a leaf
+    shift: u32,
+}
+
+impl<T: Clone> PVec<T> {
+    /// Empty vector
+    pub fn new() -> Self {
+        PVec { root: Rc::new(Node::Leaf(Vec::new())), len: 0, shift: 0 }
+    }
+
+    /// Count of elements
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the vector is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Element at index i, [`None`] if out of bounds
+    ///
+    /// # Arguments
+    /// * `i`: [`usize`]
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.len {
+            return None;
+        }
+        let mut node = &*self.root;
+        let mut level = self.shift;
+        loop {
+            match node {
+                Node::Branch(children) => {
+                    node = &children[(i >> level) & MASK];
+                    level -= BITS;
+                }
+                Node::Leaf(items) => return items.get(i & MASK)
+            }
+        }
+    }
+
+    /// New version with x appended
+    ///
+    /// # Arguments
+    /// * `x`: T
+    pub fn push(&self, x: T) -> Self {
+        if self.len == 1 << (self.shift + BITS) {
+            let branch = Node::Branch(vec![self.root.clone(), Rc::new(new_path(self.shift, x))]);
+            PVec { root: Rc::new(branch), len: self.len + 1, shift: self.shift + BITS }
+        } else {
+            PVec { root: Rc::new(push_node(&self.root, self.shift, self.len, x)), len: self.len + 1, shift: self.shift }
+        }
+    }
+
+    /// New version with element at index i replaced by x
+    ///
+    /// # Arguments
+    /// * `i`: [`usize`]
+    /// * `x`: T
+    ///
+    /// # Panics
+    /// if i >= len
+    pub fn update(&self, i: usize, x: T) -> Self {
+        assert!(i < self.len, "index {} out of bounds of PVec of length {}", i, self.len);
+        PVec { root: Rc::new(update_node(&self.root, self.shift, i, x)), len: self.len, shift: self.shift }
+    }
+
+    /// Iterate over references of elements, walking the leaves in O(n)
+    pub fn iter(&self) -> impl Iterator<Item=&T> {
+        let (mut branches, mut leaf) = match &*self.root {
+            Node::Branch(children) => (vec![children.iter()], [].iter()),
+            Node::Leaf(items) => (Vec::new(), items.iter())
+        };
+        from_fn(move || loop {
+            if let Some(x) = leaf.next() {
+                return Some(x);
+            }
+            match branches.last_mut()?.next() {
+                Some(child) => match &**child {
+                    Node::Branch(children) => branches.push(children.iter()),
+                    Node::Leaf(items) => leaf = items.iter()
+                },
+                None => {
+                    branches.pop();
+                }
+            }
+        })
+    }
+}
+
+fn new_path<T>(level: u32, x: T) -> Node<T> {
+    if level == 0 {
+        Node::Leaf(vec![x])
+    } else {
+        Node::Branch(vec![Rc::new(new_path(level - BITS, x))])
+    }
+}
+
+fn push_node<T: Clone>(node: &Node<T>, level: u32, i: usize, x: T) -> Node<T> {
+    match node {
+        Node::Leaf(items) => {
+            let mut items = items.clone();
+            items.push(x);
+            Node::Leaf(items)
+        }
+        Node::Branch(children) => {
+            let mut children = children.clone();
+            let idx = (i >> level) & MASK;
+            if idx < children.len() {
+                children[idx] = Rc::new(push_node(&children[idx], level - BITS, i, x));
+            } else {
+                children.push(Rc::new(new_path(level - BITS, x)));
+            }
+            Node::Branch(children)
+        }
+    }
+}
+
+fn update_node<T: Clone>(node: &Node<T>, level: u32, i: usize, x: T) -> Node<T> {
+    match node {
+        Node::Leaf(items) => {
+            let mut items = items.clone();
+            items[i & MASK] = x;
+            Node::Leaf(items)
+        }
+        Node::Branch(children) => {
+            let mut children = children.clone();
+            let idx = (i >> level) & MASK;
+            children[idx] = Rc::new(update_node(&children[idx], level - BITS, i, x));
+            Node::Branch(children)
+        }
+    }
+}
+
+impl<T: Clone> Default for PVec<T> {
+    fn default() -> Self {
+        PVec::new()
+    }
+}
+
+impl<T> Clone for PVec<T> {
+    fn clone(&self) -> Self {
+        PVec { root: self.root.clone(), len: self.len, shift: self.shift }
+    }
+}
+
+impl<T: Clone> FromIterator<T> for PVec<T> {
+    fn from_iter<I: IntoIterator<Item=T>>(it: I) -> Self {
+        it.into_iter().fold(PVec::new(), |acc, x| acc.push(x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Node, PVec};
+    use std::collections::HashSet;
+    use std::rc::Rc;
+
+    fn nodes<T>(v: &PVec<T>) -> HashSet<*const Node<T>> {
+        let mut ret = HashSet::new();
+        let mut stack = vec![&v.root];
+        while let Some(node) = stack.pop() {
+            ret.insert(Rc::as_ptr(node));
+            if let Node::Branch(children) = &**node {
+                stack.extend(children.iter());
+            }
+        }
+        ret
+    }
+
+    #[test]
+    fn update_shares_structure() {
+        let v: PVec<usize> = (0..10_000).collect();
+        let w = v.update(5_000, 0);
+        let (old, new) = (nodes(&v), nodes(&w));
+        assert_eq!(old.len(), new.len());
+        // 10_000 elements take 3 levels, only the path to the updated leaf is copied
+        assert_eq!(old.intersection(&new).count(), old.len() - 3);
+
+        let pushed = v.push(10_000);
+        assert_eq!(nodes(&pushed).intersection(&old).count(), old.len() - 3);
+    }
+}
\ No newline at end of file
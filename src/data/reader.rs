@@ -0,0 +1,78 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reader, a computation reading from a shared environment, so that functions depending on
+//! the same context can be composed without passing it explicitly.
+
+/// Computation of A reading from environment E
+pub struct Reader<'a, E, A>(Box<dyn Fn(&E) -> A + 'a>);
+
+impl<'a, E: 'a, A: 'a> Reader<'a, E, A> {
+    /// Build a Reader from function of environment
+    ///
+    /// # Arguments
+    /// * `f`: f :: &E -> A
+    pub fn new(f: impl Fn(&E) -> A + 'a) -> Self {
+        Reader(Box::new(f))
+    }
+
+    /// Reader ignoring the environment and always returning a
+    ///
+    /// # Arguments
+    /// * `a`: A, A must impl [`Clone`]
+    pub fn pure(a: A) -> Self where A: Clone {
+        Reader::new(move |_| a.clone())
+    }
+
+    /// Run with the environment
+    ///
+    /// # Arguments
+    /// * `env`: &E
+    pub fn run(&self, env: &E) -> A {
+        (self.0)(env)
+    }
+
+    /// Map the result by f
+    ///
+    /// # Arguments
+    /// * `f`: f :: A -> B
+    pub fn map<B: 'a>(self, f: impl Fn(A) -> B + 'a) -> Reader<'a, E, B> {
+        Reader::new(move |env| f(self.run(env)))
+    }
+
+    /// Chain with a Reader depending on the result, both read the same environment
+    ///
+    /// # Arguments
+    /// * `f`: f :: A -> Reader E B
+    pub fn and_then<B: 'a>(self, f: impl Fn(A) -> Reader<'a, E, B> + 'a) -> Reader<'a, E, B> {
+        Reader::new(move |env| f(self.run(env)).run(env))
+    }
+
+    /// Pair the results of two Readers of the same environment
+    ///
+    /// # Arguments
+    /// * `other`: Reader E B
+    pub fn zip<B: 'a>(self, other: Reader<'a, E, B>) -> Reader<'a, E, (A, B)> {
+        Reader::new(move |env| (self.run(env), other.run(env)))
+    }
+}
+
+impl<'a, E: Clone + 'a> Reader<'a, E, E> {
+    /// Reader returning the environment itself
+    pub fn ask() -> Self {
+        Reader::new(E::clone)
+    }
+}
+
+/// Run f with env, a lightweight way to scope a shared environment without building a [`Reader`]
+///
+/// # Arguments
+/// * `env`: E
+/// * `f`: f :: &E -> A, e.g. `|env| reader.run(env)`
+pub fn with_env<E, A>(env: E, f: impl FnOnce(&E) -> A) -> A {
+    f(&env)
+}
@@ -0,0 +1,89 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! State, a computation threading a state explicitly from one step to the next.
+
+/// Computation of A with state S, i.e. S -> (A, S)
+pub struct State<'a, S, A>(Box<dyn FnOnce(S) -> (A, S) + 'a>);
+
+impl<'a, S: 'a, A: 'a> State<'a, S, A> {
+    /// Build a State from transition function
+    ///
+    /// # Arguments
+    /// * `f`: f :: S -> (A, S)
+    pub fn new(f: impl FnOnce(S) -> (A, S) + 'a) -> Self {
+        State(Box::new(f))
+    }
+
+    /// State returning a without touching the state
+    ///
+    /// # Arguments
+    /// * `a`: A
+    pub fn pure(a: A) -> Self {
+        State::new(move |s| (a, s))
+    }
+
+    /// Run from the initial state, return the result and the final state
+    ///
+    /// # Arguments
+    /// * `initial`: S
+    pub fn run(self, initial: S) -> (A, S) {
+        (self.0)(initial)
+    }
+
+    /// Map the result by f
+    ///
+    /// # Arguments
+    /// * `f`: f :: A -> B
+    pub fn map<B: 'a>(self, f: impl FnOnce(A) -> B + 'a) -> State<'a, S, B> {
+        State::new(move |s| {
+            let (a, s) = self.run(s);
+            (f(a), s)
+        })
+    }
+
+    /// Chain with a State depending on the result, the state is threaded through both
+    ///
+    /// # Arguments
+    /// * `f`: f :: A -> State S B
+    pub fn and_then<B: 'a>(self, f: impl FnOnce(A) -> State<'a, S, B> + 'a) -> State<'a, S, B> {
+        State::new(move |s| {
+            let (a, s) = self.run(s);
+            f(a).run(s)
+        })
+    }
+}
+
+/// State returning current state
+pub fn get<'a, S: Clone + 'a>() -> State<'a, S, S> {
+    State::new(|s: S| (s.clone(), s))
+}
+
+/// State replacing current state by s
+///
+/// # Arguments
+/// * `s`: S
+pub fn put<'a, S: 'a>(s: S) -> State<'a, S, ()> {
+    State::new(move |_| ((), s))
+}
+
+/// State updating current state by f
+///
+/// # Arguments
+/// * `f`: f :: S -> S
+pub fn modify<'a, S: 'a>(f: impl FnOnce(S) -> S + 'a) -> State<'a, S, ()> {
+    State::new(move |s| ((), f(s)))
+}
+
+/// Run computation from initial state, return the result and the final state
+///
+/// # Arguments
+/// * `initial`: S
+/// * `computation`: State S A
+pub fn run_state<'a, S: 'a, A: 'a>(initial: S, computation: State<'a, S, A>) -> (A, S) {
+    computation.run(initial)
+}
@@ -0,0 +1,76 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Writer, a value paired with a log accumulated by [`Monoid`] along the computation.
+
+use typeclass::monoid::Monoid;
+
+/// Value A with accumulated log W
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Writer<W, A> {
+    value: A,
+    log: W,
+}
+
+impl<W: Monoid, A> Writer<W, A> {
+    /// Build a Writer from value and log
+    ///
+    /// # Arguments
+    /// * `value`: A
+    /// * `log`: W
+    pub fn new(value: A, log: W) -> Self {
+        Writer { value, log }
+    }
+
+    /// Writer of value with empty log
+    ///
+    /// # Arguments
+    /// * `value`: A
+    pub fn pure(value: A) -> Self {
+        Writer::new(value, W::empty())
+    }
+
+    /// Borrow the value
+    pub fn value(&self) -> &A {
+        &self.value
+    }
+
+    /// Borrow the log
+    pub fn log(&self) -> &W {
+        &self.log
+    }
+
+    /// Unwrap into value and log
+    pub fn run(self) -> (A, W) {
+        (self.value, self.log)
+    }
+
+    /// Map the value by f, log is kept
+    ///
+    /// # Arguments
+    /// * `f`: f :: A -> B
+    pub fn map<B>(self, f: impl FnOnce(A) -> B) -> Writer<W, B> {
+        Writer::new(f(self.value), self.log)
+    }
+
+    /// Chain with a Writer depending on the value, logs are combined in order
+    ///
+    /// # Arguments
+    /// * `f`: f :: A -> Writer W B
+    pub fn and_then<B>(self, f: impl FnOnce(A) -> Writer<W, B>) -> Writer<W, B> {
+        let next = f(self.value);
+        Writer::new(next.value, self.log.combine(next.log))
+    }
+}
+
+/// Writer appending w to the log, with no meaningful value
+///
+/// # Arguments
+/// * `w`: W, W must impl [`Monoid`]
+pub fn tell<W: Monoid>(w: W) -> Writer<W, ()> {
+    Writer::new((), w)
+}
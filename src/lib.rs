@@ -12,7 +12,33 @@
 // for crate docs
 // #![feature(universal_impl_trait,conservative_impl_trait)]
 
+extern crate kaguya_macros;
+#[cfg(feature = "rand")]
+extern crate rand;
+
+pub use kaguya_macros::curried;
+
 pub mod basic_fn {
     pub mod fun;
     pub mod mac;
+    pub mod control;
+    pub mod strings;
+    pub mod rec;
+    pub mod transducer;
+    pub mod memo;
+    #[cfg(feature = "rand")]
+    pub mod random;
+    pub mod diff;
+}
+
+pub mod typeclass {
+    pub mod monoid;
+}
+
+pub mod data {
+    pub mod reader;
+    pub mod writer;
+    pub mod state;
+    pub mod list;
+    pub mod pvec;
 }
\ No newline at end of file
@@ -0,0 +1,74 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Typeclass Monoid, a type with an associative binary operation and an identity element.
+
+use std::iter;
+use std::ops::{Add, Mul};
+
+/// Monoid typeclass
+///
+/// Law:
+/// 1. combine(empty(), x) == x == combine(x, empty())
+/// 2. combine(combine(x, y), z) == combine(x, combine(y, z))
+pub trait Monoid {
+    /// identity element
+    fn empty() -> Self;
+    /// associative binary operation
+    fn combine(self, other: Self) -> Self;
+}
+
+/// Monoid under addition of T
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sum<T>(pub T);
+
+/// Monoid under multiplication of T
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Product<T>(pub T);
+
+impl<T: iter::Sum + Add<Output=T>> Monoid for Sum<T> {
+    fn empty() -> Self {
+        Sum(iter::empty().sum())
+    }
+    fn combine(self, other: Self) -> Self {
+        Sum(self.0 + other.0)
+    }
+}
+
+impl<T: iter::Product + Mul<Output=T>> Monoid for Product<T> {
+    fn empty() -> Self {
+        Product(iter::empty().product())
+    }
+    fn combine(self, other: Self) -> Self {
+        Product(self.0 * other.0)
+    }
+}
+
+impl Monoid for String {
+    fn empty() -> Self {
+        String::new()
+    }
+    fn combine(mut self, other: Self) -> Self {
+        self.push_str(&other);
+        self
+    }
+}
+
+impl<T> Monoid for Vec<T> {
+    fn empty() -> Self {
+        Vec::new()
+    }
+    fn combine(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+impl Monoid for () {
+    fn empty() -> Self {}
+    fn combine(self, _: Self) -> Self {}
+}
@@ -4,10 +4,11 @@
 // http://www.apache.org/licenses/LICENSE-2.0>, at your
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
-#![feature(trace_macros)]
 
 #[macro_use]
 extern crate kaguya_rs;
+#[cfg(feature = "rand")]
+extern crate rand;
 
 #[test]
 /// macro compose!
@@ -75,6 +76,7 @@ fn pipe_type_projection() {
 
 #[test]
 // fn map and curry map
+#[allow(clippy::useless_vec)]
 fn map() {
     use kaguya_rs::basic_fn::fun::map;
     let v = vec![1,2,3];
@@ -88,6 +90,7 @@ fn map() {
 
 #[test]
 // fn filter and curry filter
+#[allow(clippy::useless_vec, clippy::map_clone)]
 fn filter() {
     use kaguya_rs::basic_fn::fun::filter;
     let v = vec![1,2,3];
@@ -101,6 +104,7 @@ fn filter() {
 
 #[test]
 // fn filter_not and curry filter_not
+#[allow(clippy::useless_vec, clippy::map_clone)]
 fn filter_not() {
     use kaguya_rs::basic_fn::fun::filter_not;
     let v = vec![1,2,3];
@@ -114,6 +118,7 @@ fn filter_not() {
 
 #[test]
 // fn foldl and curry foldl
+#[allow(clippy::useless_vec)]
 fn foldl() {
     use kaguya_rs::basic_fn::fun::foldl;
     let v = vec![1,2,3];
@@ -135,6 +140,7 @@ fn foldl() {
 
 #[test]
 // fn foldr and curry foldr
+#[allow(clippy::useless_vec)]
 fn foldr() {
     use kaguya_rs::basic_fn::fun::foldr;
     let v = vec!["Houraisan","Kaguya"];
@@ -156,12 +162,19 @@ fn foldr() {
 
 #[test]
 // fn sum and macro sum
+#[allow(clippy::useless_vec)]
 fn sum() {
     use kaguya_rs::basic_fn::fun::sum;
     let result = 10;
     assert_eq!(sum(1..=4), result);
     assert_eq!(sum!(1;4), result);
     assert_eq!(sum!(1,2,3,4), result);
+
+    // integer and float types
+    assert_eq!(sum(vec![1_i64, 2, 3]), 6_i64);
+    assert_eq!(sum(vec![1_u8, 2, 3].iter().cloned()), 6_u8);
+    assert_eq!(sum(vec![1.0, 2.0, 3.0]), 6.0);
+    assert_eq!(sum!(1.5, 2.5), 4.0);
 }
 
 #[test]
@@ -179,6 +192,7 @@ fn ls() {
 
 #[test]
 // fn head
+#[allow(clippy::useless_vec)]
 fn head() {
     use kaguya_rs::basic_fn::fun::head;
     let empty_vec = Vec::new() as Vec<i8>;
@@ -190,6 +204,7 @@ fn head() {
 
 #[test]
 // fn tail
+#[allow(clippy::useless_vec)]
 fn tail() {
     use kaguya_rs::basic_fn::fun::tail;
     let empty_vec = Vec::new() as Vec<i8>;
@@ -201,6 +216,7 @@ fn tail() {
 
 #[test]
 // fn last
+#[allow(clippy::useless_vec)]
 fn last() {
     use kaguya_rs::basic_fn::fun::last;
     let empty_vec = Vec::new() as Vec<i8>;
@@ -212,6 +228,7 @@ fn last() {
 
 #[test]
 // fn init
+#[allow(clippy::useless_vec)]
 fn init() {
     use kaguya_rs::basic_fn::fun::init;
     let empty_vec = Vec::new() as Vec<i8>;
@@ -223,6 +240,7 @@ fn init() {
 
 #[test]
 // fn and macro skip
+#[allow(clippy::useless_vec)]
 fn skip() {
     use kaguya_rs::basic_fn::fun::skip;
     let empty_vec = Vec::new() as Vec<i8>;
@@ -238,6 +256,7 @@ fn skip() {
 
 #[test]
 // fn and macro take
+#[allow(clippy::useless_vec)]
 fn take() {
     use kaguya_rs::basic_fn::fun::take;
     let empty_rec = Vec::new() as Vec<i8>;
@@ -253,16 +272,24 @@ fn take() {
 
 #[test]
 // fn and macro product
+#[allow(clippy::useless_vec)]
 fn product() {
     use kaguya_rs::basic_fn::fun::product;
     assert_eq!(product(1..=5), 120);
 
     assert_eq!(product!(1;5), 120);
     assert_eq!(product!(1,2,3,4,5), 120);
+
+    // integer and float types
+    assert_eq!(product(vec![2_i64, 3, 4]), 24_i64);
+    assert_eq!(product(vec![2_u8, 3].iter().cloned()), 6_u8);
+    assert_eq!(product(vec![0.5, 2.0, 3.0]), 3.0);
+    assert_eq!(product!(1.5, 2.0), 3.0);
 }
 
 #[test]
 // fn length
+#[allow(clippy::useless_vec)]
 fn length() {
     use kaguya_rs::basic_fn::fun::length;
     assert_eq!(length(vec![1,2,3,4,5].iter()), 5);
@@ -278,6 +305,7 @@ fn reverse() {
 
 #[test]
 // fn and macro concat
+#[allow(clippy::useless_vec)]
 fn concat() {
     use kaguya_rs::basic_fn::fun::concat;
     assert_eq!(concat(0..1,1..2), vec![0,1]);
@@ -359,6 +387,7 @@ fn rem() {
 
 #[test]
 // macro odd/even
+#[allow(clippy::assertions_on_constants)]
 fn odd_and_even() {
     assert!(even!(2));
     assert!(odd!(1));
@@ -424,6 +453,7 @@ fn find() {
 
 #[test]
 // fn sorted
+#[allow(clippy::map_clone)]
 fn sorted() {
     use kaguya_rs::basic_fn::fun::sorted;
     let vec = vec![1,5,3,7,8,3,9,3,2];
@@ -432,6 +462,7 @@ fn sorted() {
 
 #[test]
 // fn and macro sorted_by
+#[allow(clippy::useless_vec, clippy::map_clone, clippy::needless_lifetimes)]
 fn sorted_by() {
     use kaguya_rs::basic_fn::fun::sorted_by;
     use std::cmp::Ordering;
@@ -460,6 +491,7 @@ fn sorted_by() {
 
 #[test]
 // fn and macro zip
+#[allow(clippy::useless_vec)]
 fn zip() {
     use kaguya_rs::basic_fn::fun::zip;
     let ls1 = vec![1,2,3];
@@ -473,6 +505,7 @@ fn zip() {
 
 #[test]
 // fn and macro zip_with
+#[allow(clippy::useless_vec)]
 fn zip_with() {
     use kaguya_rs::basic_fn::fun::zip_with;
     let ls1 = vec![1,2,3];
@@ -486,4 +519,1123 @@ fn zip_with() {
     assert_eq!(mac1(ls1.iter(), ls2.iter()).collect::<Vec<_>>(), vec![false, true, false]);
     assert_eq!(mac2(ls1.iter())(ls2.iter()).collect::<Vec<_>>(), vec![false, true, false]);
     assert_eq!(mac3(ls2.iter()).collect::<Vec<_>>(), vec![false, true, false]);
+}
+
+#[test]
+// fn stride, take_even and take_odd
+fn stride() {
+    use kaguya_rs::basic_fn::fun::{stride, take_even, take_odd};
+    assert_eq!(stride(3, 0..=9).collect::<Vec<_>>(), vec![0,3,6,9]);
+    assert_eq!(take_even(vec![10,20,30,40].into_iter()).collect::<Vec<_>>(), vec![10,30]);
+    assert_eq!(take_odd(vec![10,20,30,40].into_iter()).collect::<Vec<_>>(), vec![20,40]);
+    assert_eq!(take_odd(0..0).collect::<Vec<i32>>(), vec![]);
+}
+
+#[test]
+// fn and macro cons, fn snoc
+fn cons_and_snoc() {
+    use kaguya_rs::basic_fn::fun::{cons, snoc};
+    assert_eq!(cons(0, 1..=3).collect::<Vec<_>>(), vec![0,1,2,3]);
+    assert_eq!(snoc(1..=3, 4).collect::<Vec<_>>(), vec![1,2,3,4]);
+    // lazy on infinite iterator
+    assert_eq!(cons(0, 1..).take(3).collect::<Vec<_>>(), vec![0,1,2]);
+
+    assert_eq!(cons!(0, 1..=2).collect::<Vec<_>>(), vec![0,1,2]);
+    let f = cons!(0);
+    assert_eq!(f(1..=2).collect::<Vec<_>>(), vec![0,1,2]);
+}
+
+#[test]
+// fn uncons
+fn uncons() {
+    use kaguya_rs::basic_fn::fun::uncons;
+    assert!(uncons(0..0).is_none());
+
+    let (x, rest) = uncons(0..).unwrap();
+    assert_eq!(x, 0);
+    assert_eq!(rest.take(3).collect::<Vec<_>>(), vec![1,2,3]);
+}
+
+#[test]
+// fn grid_indices
+fn grid_indices() {
+    use kaguya_rs::basic_fn::fun::grid_indices;
+    assert_eq!(grid_indices(2, 3).collect::<Vec<_>>(), vec![(0,0),(0,1),(0,2),(1,0),(1,1),(1,2)]);
+    assert_eq!(grid_indices(0, 3).count(), 0);
+    assert_eq!(grid_indices(3, 0).count(), 0);
+}
+
+#[test]
+// fn and macro apply, fn apply_to
+fn apply() {
+    use kaguya_rs::basic_fn::fun::{apply, apply_to, map};
+    assert_eq!(apply(|x| x+1, 1), 2);
+    assert_eq!(apply!(|x| x+1, 1), 2);
+    assert_eq!(apply!(|x: i32| x+1)(1), 2);
+
+    let fs: Vec<fn(i32) -> i32> = vec![|x| x+1, |x| x*2];
+    let f = map!(apply_to(3));
+    assert_eq!(f(fs.clone().into_iter()).collect::<Vec<_>>(), vec![4,6]);
+    let f2 = map!(apply!(_, 3));
+    assert_eq!(f2(fs.into_iter()).collect::<Vec<_>>(), vec![4,6]);
+}
+
+#[test]
+/// macro thread!
+fn thread() {
+    assert_eq!(thread!(3, |x| x+1, |x| x*2), 8);
+    assert_eq!(thread!(3), 3);
+
+    fn add_one(x: i64) -> i64 {
+        x + 1
+    }
+    assert_eq!(thread!("Kaguya", |x: &str| x.len() as i64, add_one), 7);
+}
+
+#[test]
+/// macro let_pipe!
+fn let_pipe() {
+    assert_eq!(let_pipe!(x = 3; y = x + 1; y * 2), 8);
+    // third stage refers to first stage
+    assert_eq!(let_pipe!(x = 3; y = x * 10; y + x), 33);
+    assert_eq!(let_pipe!(1 + 1), 2);
+}
+
+#[test]
+// macro maybe
+fn maybe() {
+    let f = |x: i32| maybe!(x > 0, x * 2);
+    assert_eq!(f(2), Some(4));
+    assert_eq!(f(-2), None);
+    assert_eq!(maybe!(1), Some(1));
+
+    // value is not evaluated if condition fails
+    let v: Vec<i32> = Vec::new();
+    assert_eq!(maybe!(!v.is_empty(), v[0]), None);
+    assert_eq!(vec![-1, 2, 3].into_iter().filter_map(f).collect::<Vec<_>>(), vec![4, 6]);
+}
+
+#[test]
+// fn attempt
+fn attempt() {
+    use kaguya_rs::basic_fn::control::attempt;
+    assert_eq!(attempt(|| 1 + 1).ok(), Some(2));
+
+    let err = attempt(|| -> i32 { panic!("boom") }).unwrap_err();
+    assert_eq!(err.downcast_ref::<&str>(), Some(&"boom"));
+}
+
+#[test]
+/// macro compose_boxed!
+fn compose_boxed() {
+    let fs: Vec<Box<dyn Fn(i32) -> i32>> = vec![
+        compose_boxed!(|x| x+1, |x| x*2),
+        compose_boxed!(|x| x*2, |x| x+1),
+        compose_boxed!(|x| x-1, |x| x*x, |x| x+1)
+    ];
+    assert_eq!(fs.iter().map(|f| f(3)).collect::<Vec<_>>(), vec![7, 8, 15]);
+
+    struct Holder {
+        f: Box<dyn Fn(i32) -> i32>
+    }
+    fn make() -> Box<dyn Fn(i32) -> i32> {
+        compose_boxed!(|x| x+1, |x| x*2)
+    }
+    let h = Holder { f: make() };
+    assert_eq!((h.f)(-1), -1);
+
+    // already a trait object without any outer annotation, so both arms share one type
+    let step = 10;
+    let f = compose_boxed!(|x: i32| x + step, |x: i32| x * 2);
+    let g = if step > 0 { f } else { compose_boxed!(|x: i32| x - step, |x: i32| x) };
+    assert_eq!(g(1), 12);
+    let held = Holder { f: compose_boxed!(|x| x * 3, |x| x - 1) };
+    assert_eq!((held.f)(2), 3);
+}
+
+#[test]
+// macro dbg_tap
+fn dbg_tap() {
+    assert_eq!(dbg_tap!(1 + 1), 2);
+    let s = "Kaguya".to_string();
+    assert_eq!(dbg_tap!(s), "Kaguya");
+
+    let f = pipe!(|x| x + 1, dbg_tap!(), |x| x * 2);
+    assert_eq!(f(3), 8);
+}
+
+#[test]
+// fn zip_map
+fn zip_map() {
+    use kaguya_rs::basic_fn::fun::zip_map;
+    assert_eq!(zip_map(|x, y| x * y, [1,2,3], vec![4,5,6]).collect::<Vec<_>>(), vec![4,10,18]);
+    assert_eq!(zip_map(|x, y: char| (x, y), 0.., "ab".chars()).collect::<Vec<_>>(), vec![(0,'a'), (1,'b')]);
+}
+
+#[test]
+// core functions accept IntoIterator
+fn into_iterator_args() {
+    use kaguya_rs::basic_fn::fun::{map, filter, filter_not, foldl, foldr, head, tail, last, init,
+        skip, take, length, reverse, concat, min, max, sorted, zip, cons, snoc};
+    let v = vec![3,1,2];
+    let arr = [3,1,2];
+
+    // Vec
+    assert_eq!(map(|x| x+1, v.clone()).collect::<Vec<_>>(), vec![4,2,3]);
+    assert_eq!(filter(|&x| x > 1, v.clone()).collect::<Vec<_>>(), vec![3,2]);
+    assert_eq!(foldr(0, |x,y| x*10+y, v.clone()), 213);
+    assert_eq!(head(v.clone()), Some(3));
+    assert_eq!(init(v.clone()), Some(vec![3,1]));
+    assert_eq!(sorted(v.clone()).collect::<Vec<_>>(), vec![1,2,3]);
+    assert_eq!(cons(0, v.clone()).collect::<Vec<_>>(), vec![0,3,1,2]);
+
+    // array
+    assert_eq!(filter_not(|&x| x > 1, arr).collect::<Vec<_>>(), vec![1]);
+    assert_eq!(foldl(0, |x,y| x*10+y, arr), 312);
+    assert_eq!(tail(arr), Some(vec![1,2]));
+    assert_eq!(last(arr), Some(2));
+    assert_eq!(skip(1, arr), vec![1,2]);
+    assert_eq!(take(1, arr), vec![3]);
+    assert_eq!(reverse(arr), vec![2,1,3]);
+    assert_eq!(snoc(arr, 4).collect::<Vec<_>>(), vec![3,1,2,4]);
+
+    // slice
+    assert_eq!(length(arr[..2].iter()), 2);
+    assert_eq!(min(&v[..]), Some(&1));
+    assert_eq!(max(&arr[..]), Some(&3));
+
+    // mixed
+    assert_eq!(concat(arr, v.clone()), vec![3,1,2,3,1,2]);
+    assert_eq!(zip(arr, v.iter()).collect::<Vec<_>>(), vec![(3,&3),(1,&1),(2,&2)]);
+    let f = map!(|x| x*2);
+    assert_eq!(f(v).collect::<Vec<_>>(), vec![6,2,4]);
+}
+
+#[test]
+// fn collect_string
+fn collect_string() {
+    use kaguya_rs::basic_fn::fun::map;
+    use kaguya_rs::basic_fn::strings::collect_string;
+    let f = pipe!(map!(|c: char| c.to_ascii_uppercase()), collect_string);
+    assert_eq!(f("abc".chars()), "ABC");
+    assert_eq!(collect_string(vec!["Houraisan", " ", "Kaguya"]), "Houraisan Kaguya");
+    assert_eq!(collect_string(vec!["美し".to_string(), "い".to_string()]), "美しい");
+}
+
+#[test]
+// fn chars, bytes, to_upper and to_lower
+fn chars_bytes_and_case() {
+    use kaguya_rs::basic_fn::strings::{chars, bytes, to_upper, to_lower};
+    assert_eq!(chars("かぐや").count(), 3);
+    assert_eq!(bytes("かぐや").count(), 9);
+    assert_eq!(to_upper("Kaguya"), "KAGUYA");
+    assert_eq!(to_lower("KaGuYa"), "kaguya");
+    assert_eq!(to_lower("ΣΑΣ"), "σας");
+
+    let f = pipe!(to_upper, |s: String| chars(&s).count());
+    assert_eq!(f("kaguya"), 6);
+    assert_eq!(f("straße"), 7);
+}
+
+#[test]
+// fn reverse_str
+fn reverse_str() {
+    use kaguya_rs::basic_fn::strings::reverse_str;
+    assert_eq!(reverse_str("abc"), "cba");
+    assert_eq!(reverse_str("かぐや姫"), "姫やぐか");
+    assert_eq!(reverse_str(""), "");
+}
+
+#[test]
+// fn trim, trim_start, trim_end and map_words
+fn trim_and_map_words() {
+    use kaguya_rs::basic_fn::strings::{trim, trim_start, trim_end, map_words};
+    assert_eq!(trim("  Kaguya \n"), "Kaguya");
+    assert_eq!(trim_start("  Kaguya "), "Kaguya ");
+    assert_eq!(trim_end("  Kaguya "), "  Kaguya");
+
+    assert_eq!(map_words(|w| w.to_uppercase(), "  hello world "), "HELLO WORLD");
+    assert_eq!(map_words(|w| w.chars().rev().collect(), "ab\t cd"), "ba dc");
+    assert_eq!(map_words(|w| w.to_string(), "   "), "");
+}
+
+#[test]
+// fn zip_longest and zip_longest_default
+fn zip_longest() {
+    use kaguya_rs::basic_fn::fun::{zip_longest, zip_longest_default};
+    assert_eq!(zip_longest(vec![1,2], vec!['a']).collect::<Vec<_>>(), vec![(Some(1), Some('a')), (Some(2), None)]);
+    assert_eq!(zip_longest(0..0, vec!['a']).collect::<Vec<(Option<i32>, _)>>(), vec![(None, Some('a'))]);
+
+    assert_eq!(zip_longest_default(vec![1,2], vec![9]).collect::<Vec<(i32, i32)>>(), vec![(1,9),(2,0)]);
+    assert_eq!(zip_longest_default(vec![1], vec!["a".to_string(), "b".to_string()]).collect::<Vec<_>>(),
+        vec![(1, "a".to_string()), (0, "b".to_string())]);
+}
+
+#[test]
+// fn punctuate
+fn punctuate() {
+    use kaguya_rs::basic_fn::strings::punctuate;
+    assert_eq!(punctuate(", ", " and ", vec!["a"]), "a");
+    assert_eq!(punctuate(", ", " and ", vec!["a", "b"]), "a and b");
+    assert_eq!(punctuate(", ", " and ", vec!["a", "b", "c"]), "a, b and c");
+    assert_eq!(punctuate(", ", " or ", 1..=4), "1, 2, 3 or 4");
+    assert_eq!(punctuate(", ", " and ", Vec::<i32>::new()), "");
+}
+
+#[test]
+// fn reduce_right
+fn reduce_right() {
+    use kaguya_rs::basic_fn::fun::reduce_right;
+    assert_eq!(reduce_right(|a, b| a - b, vec![1,2,3,4]), Some(1-(2-(3-4))));
+    assert_eq!(reduce_right(|a, b| a - b, vec![1]), Some(1));
+    assert_eq!(reduce_right(|a: i32, b| a - b, vec![]), None);
+}
+
+#[test]
+// fn take_while_map
+fn take_while_map() {
+    use kaguya_rs::basic_fn::fun::take_while_map;
+    let (prefix, rest) = take_while_map(|&x| x < 4, |x| x * 10, 1..);
+    assert_eq!(prefix, vec![10,20,30]);
+    assert_eq!(rest.take(2).collect::<Vec<_>>(), vec![4,5]);
+
+    let (prefix, rest) = take_while_map(|&x| x < 4, |x| x * 10, vec![5,1]);
+    assert_eq!(prefix, Vec::<i32>::new());
+    assert_eq!(rest.collect::<Vec<_>>(), vec![5,1]);
+}
+
+#[test]
+// fn pairwise, deltas and integrate
+fn deltas_and_integrate() {
+    use kaguya_rs::basic_fn::fun::{pairwise, deltas, integrate};
+    assert_eq!(pairwise(vec![1,2,3]).collect::<Vec<_>>(), vec![(1,2),(2,3)]);
+    assert_eq!(pairwise(vec![1]).count(), 0);
+
+    assert_eq!(deltas(vec![1,3,6]).collect::<Vec<_>>(), vec![2,3]);
+    // integrate from 0 recovers the input offset by its first element
+    assert_eq!(integrate(0, deltas(vec![1,3,6])).collect::<Vec<_>>(), vec![0,2,5]);
+    assert_eq!(integrate(1, deltas(vec![1,3,6])).collect::<Vec<_>>(), vec![1,3,6]);
+    assert_eq!(integrate(0.5, vec![0.5, 1.0]).collect::<Vec<_>>(), vec![0.5, 1.0, 2.0]);
+}
+
+#[test]
+// fn run_lengths
+fn run_lengths() {
+    use kaguya_rs::basic_fn::fun::run_lengths;
+    assert_eq!(run_lengths(vec!['a','a','b','c','c','c']).collect::<Vec<_>>(), vec![2,1,3]);
+    assert_eq!(run_lengths(Vec::<i32>::new()).count(), 0);
+}
+
+#[test]
+// fn zip_apply
+fn zip_apply() {
+    use kaguya_rs::basic_fn::fun::zip_apply;
+    let fs: Vec<fn(i32) -> i32> = vec![|x| x+1, |x| x*10];
+    assert_eq!(zip_apply(fs.clone(), vec![5, 5]).collect::<Vec<_>>(), vec![6, 50]);
+    assert_eq!(zip_apply(fs, vec![5]).collect::<Vec<_>>(), vec![6]);
+}
+
+#[test]
+// fn fold_map and fold_map_indexed
+fn fold_map() {
+    use kaguya_rs::basic_fn::fun::{fold_map, fold_map_indexed};
+    use kaguya_rs::typeclass::monoid::{Sum, Product};
+    assert_eq!(fold_map(Sum, vec![1,2,3]), Sum(6));
+    assert_eq!(fold_map(|x: i32| Product(x + 1), vec![1,2,3]), Product(24));
+    assert_eq!(fold_map(|c: char| c.to_string(), "abc".chars()), "abc");
+
+    // polynomial 1 + 2x + 3x^2 at x = 2
+    assert_eq!(fold_map_indexed(|i, c: i32| Sum(c * 2i32.pow(i as u32)), vec![1,2,3]), Sum(17));
+    assert_eq!(fold_map_indexed(|i, c: char| vec![(i, c)], "ab".chars()), vec![(0,'a'), (1,'b')]);
+}
+
+#[test]
+// fn first_duplicate
+fn first_duplicate() {
+    use kaguya_rs::basic_fn::fun::first_duplicate;
+    assert_eq!(first_duplicate(vec![1,2,3,2,1]), Some(2));
+    assert_eq!(first_duplicate(vec![1,2,3]), None);
+    // short-circuit on infinite iterator
+    assert_eq!(first_duplicate((0..).map(|x| x % 5)), Some(0));
+}
+
+#[test]
+// fn flatten, flatten2 and flatten3
+fn flatten() {
+    use kaguya_rs::basic_fn::fun::{flatten, flatten2, flatten3};
+    assert_eq!(flatten(vec![vec![1], vec![], vec![2,3]]).collect::<Vec<_>>(), vec![1,2,3]);
+    assert_eq!(flatten2(vec![vec![vec![1],vec![2]], vec![vec![3]]]).collect::<Vec<_>>(), vec![1,2,3]);
+    assert_eq!(flatten3(vec![vec![vec![vec![1, 2]]], vec![vec![vec![3], vec![]]]]).collect::<Vec<_>>(), vec![1,2,3]);
+    assert_eq!(flatten(vec![Some(1), None, Some(2)]).collect::<Vec<_>>(), vec![1,2]);
+}
+
+#[test]
+// fn fixpoint and fixpoint_within
+fn fixpoint() {
+    use kaguya_rs::basic_fn::rec::{fixpoint, fixpoint_within};
+    // Newton's method of integer square root of 16
+    let newton = |x: i64| (x + 16 / x) / 2;
+    assert_eq!(fixpoint(newton, 16), 4);
+    assert_eq!(fixpoint_within(10, newton, 16), Some(4));
+    assert_eq!(fixpoint_within(2, newton, 16), None);
+
+    assert_eq!(fixpoint_within(100, |x: i64| x + 1, 0), None);
+}
+
+#[test]
+// fn zip_sum and zip_sub
+fn zip_sum_and_sub() {
+    use kaguya_rs::basic_fn::fun::{zip_sum, zip_sub};
+    assert_eq!(zip_sum([1,2,3], [10,20,30]).collect::<Vec<_>>(), vec![11,22,33]);
+    assert_eq!(zip_sub(vec![10.0,20.0], vec![0.5,1.5,2.5]).collect::<Vec<_>>(), vec![9.5,18.5]);
+}
+
+#[test]
+// fn scale and add_scalar
+fn scale_and_add_scalar() {
+    use kaguya_rs::basic_fn::fun::{scale, add_scalar, zip_sum};
+    assert_eq!(scale(3, vec![1,2,3]).collect::<Vec<_>>(), vec![3,6,9]);
+    assert_eq!(add_scalar(0.5, vec![1.0,2.0]).collect::<Vec<_>>(), vec![1.5,2.5]);
+    // 2a + b
+    assert_eq!(zip_sum(scale(2, vec![1,2]), vec![10,20]).collect::<Vec<_>>(), vec![12,24]);
+}
+
+#[test]
+// fn windowed_sum and moving_average
+fn moving_average() {
+    use kaguya_rs::basic_fn::fun::{windowed_sum, moving_average};
+    assert_eq!(windowed_sum(2, vec![1,2,3,4]).collect::<Vec<_>>(), vec![3,5,7]);
+    assert_eq!(windowed_sum(1, vec![1,2]).collect::<Vec<_>>(), vec![1,2]);
+    assert_eq!(windowed_sum(3, vec![1,2]).count(), 0);
+
+    assert_eq!(moving_average(2, vec![1.0,2.0,3.0,4.0]).collect::<Vec<_>>(), vec![1.5,2.5,3.5]);
+    assert_eq!(moving_average(3, vec![1_i32, 2, 6]).collect::<Vec<_>>(), vec![3.0]);
+    assert_eq!(moving_average(5, vec![1.0,2.0]).count(), 0);
+}
+
+#[test]
+// fn clamp_iter, clip_below and clip_above
+fn clamp_iter() {
+    use kaguya_rs::basic_fn::fun::{clamp_iter, clip_below, clip_above};
+    assert_eq!(clamp_iter(0, 10, vec![-5, 5, 20]).collect::<Vec<_>>(), vec![0, 5, 10]);
+    assert_eq!(clip_below(0.0, vec![-0.5, 0.5]).collect::<Vec<_>>(), vec![0.0, 0.5]);
+    assert_eq!(clip_above('m', "azm".chars()).collect::<String>(), "amm");
+}
+
+#[test]
+// fn argsort and argsort_by
+fn argsort() {
+    use kaguya_rs::basic_fn::fun::{argsort, argsort_by};
+    assert_eq!(argsort(vec![30,10,20]), vec![1,2,0]);
+    assert_eq!(argsort(vec![2,1,2,1]), vec![1,3,0,2]);
+    assert_eq!(argsort_by(|x: &i32, y| y.cmp(x), vec![30,10,20]), vec![0,2,1]);
+    assert_eq!(argsort(Vec::<i32>::new()), Vec::<usize>::new());
+}
+
+#[test]
+// fn reorder
+fn reorder() {
+    use kaguya_rs::basic_fn::fun::{argsort, reorder};
+    let data = vec![30,10,20];
+    let names = vec!["c","a","b"];
+    let indices = argsort(&data);
+    assert_eq!(reorder(indices.clone(), data.clone()), vec![10,20,30]);
+    assert_eq!(reorder(indices, names), vec!["a","b","c"]);
+    assert_eq!(reorder(vec![0,0], vec!['x']), vec!['x','x']);
+}
+
+#[test]
+#[should_panic]
+// fn reorder with out of range index
+fn reorder_out_of_range() {
+    use kaguya_rs::basic_fn::fun::reorder;
+    reorder(vec![3], vec![1,2,3]);
+}
+
+#[test]
+// fn rank and dense_rank
+fn rank() {
+    use kaguya_rs::basic_fn::fun::{rank, dense_rank};
+    assert_eq!(rank(vec![10,20,20,30]), vec![1.0,2.5,2.5,4.0]);
+    assert_eq!(rank(vec![3,1,3,3]), vec![3.0,1.0,3.0,3.0]);
+    assert_eq!(dense_rank(vec![10,20,20,30]), vec![1,2,2,3]);
+    assert_eq!(dense_rank(vec![30,10,30]), vec![2,1,2]);
+    assert_eq!(rank(Vec::<i32>::new()), Vec::<f64>::new());
+}
+
+#[test]
+// fn lex_cmp
+fn lex_cmp() {
+    use kaguya_rs::basic_fn::fun::lex_cmp;
+    use std::cmp::Ordering;
+    assert_eq!(lex_cmp([1,2,3], [1,2,4]), Ordering::Less);
+    assert_eq!(lex_cmp(vec![1,2], vec![1,2,0]), Ordering::Less);
+    assert_eq!(lex_cmp(vec![2], vec![1,2,0]), Ordering::Greater);
+    assert_eq!(lex_cmp("abc".chars(), "abc".chars()), Ordering::Equal);
+    // short-circuit on infinite iterators
+    assert_eq!(lex_cmp(0.., (0..).map(|x| if x == 5 { 0 } else { x })), Ordering::Greater);
+}
+
+#[test]
+// fn hamming and levenshtein
+fn hamming_and_levenshtein() {
+    use kaguya_rs::basic_fn::fun::{hamming, levenshtein};
+    assert_eq!(hamming([1,2,3], [1,0,3]), 1);
+    assert_eq!(hamming("karolin".chars(), "kathrin".chars()), 3);
+
+    assert_eq!(levenshtein("kitten".chars(), "sitting".chars()), 3);
+    assert_eq!(levenshtein("sitting".chars(), "kitten".chars()), 3);
+    assert_eq!(levenshtein("".chars(), "abc".chars()), 3);
+    assert_eq!(levenshtein(vec![1,2,3], vec![1,2,3]), 0);
+    assert_eq!(levenshtein("abc".chars(), "".chars()), 3);
+    // b is streamed, a long b only costs time
+    assert_eq!(levenshtein("ab".chars(), "ab".chars().cycle().take(10_000)), 9_998);
+}
+
+#[test]
+#[should_panic]
+// fn hamming with different lengths
+fn hamming_length_mismatch() {
+    use kaguya_rs::basic_fn::fun::hamming;
+    hamming([1,2,3], [1,2]);
+}
+
+#[test]
+// fn lcs
+fn lcs() {
+    use kaguya_rs::basic_fn::fun::lcs;
+    assert_eq!(lcs(vec![1,2,3,4], vec![2,4,3]), vec![2,4]);
+    assert_eq!(lcs("ABCBDAB".chars(), "BDCABA".chars()).len(), 4);
+    assert_eq!(lcs("abc".chars(), "xyz".chars()), vec![]);
+}
+
+#[test]
+// fn zip_eq
+fn zip_eq() {
+    use kaguya_rs::basic_fn::fun::zip_eq;
+    assert_eq!(zip_eq(vec![1,2], "ab".chars()).collect::<Vec<_>>(), vec![(1,'a'), (2,'b')]);
+}
+
+#[test]
+#[should_panic]
+// fn zip_eq with different lengths
+fn zip_eq_length_mismatch() {
+    use kaguya_rs::basic_fn::fun::zip_eq;
+    zip_eq(vec![1,2,3], vec![1,2]).count();
+}
+
+#[test]
+// fn option_fold and result_fold
+fn option_and_result_fold() {
+    use kaguya_rs::basic_fn::control::{option_fold, result_fold};
+    assert_eq!(option_fold(0, |x| x * 2, Some(3)), 6);
+    assert_eq!(option_fold(0, |x: i32| x * 2, None), 0);
+
+    let ok: Result<i32, &str> = Ok(3);
+    let err: Result<i32, &str> = Err("boom");
+    assert_eq!(result_fold(|e: &str| e.len() as i32, |x| x * 2, ok), 6);
+    assert_eq!(result_fold(|e: &str| e.len() as i32, |x| x * 2, err), 4);
+}
+
+#[test]
+// fn co_windows
+fn co_windows() {
+    use kaguya_rs::basic_fn::fun::co_windows;
+    let a = [1,2,3,4,5];
+    let b = [5,4,3,2,1];
+    assert_eq!(co_windows(2, &a, &b).count(), 4);
+    assert_eq!(co_windows(2, &a, &b).next(), Some((&[1,2][..], &[5,4][..])));
+    // cross-correlation at lag 0 of each window
+    let corr = co_windows(2, &a, &b[..3]).map(|(x, y)| x.iter().zip(y).map(|(p, q)| p * q).sum::<i32>()).collect::<Vec<_>>();
+    assert_eq!(corr, vec![13, 17]);
+}
+
+#[test]
+// fn mapaccum
+fn mapaccum() {
+    use kaguya_rs::basic_fn::fun::mapaccum;
+    assert_eq!(mapaccum(0, |s, x| (s+1, (s, x)), vec!['a','b']), (2, vec![(0,'a'),(1,'b')]));
+    // running sum as state, doubled element as output
+    assert_eq!(mapaccum(0, |s, x| (s + x, x * 2), vec![1,2,3]), (6, vec![2,4,6]));
+    assert_eq!(mapaccum(7, |s, x: i32| (s, x), vec![]), (7, vec![]));
+}
+
+#[test]
+// fn transduce
+fn transduce() {
+    use kaguya_rs::basic_fn::transducer::{transduce, mapping, filtering, taking, Transducer};
+    let xf = mapping(|x: i32| x + 1).then(filtering(|x: &i32| x % 2 == 0));
+    let push = |mut v: Vec<i32>, x| { v.push(x); v };
+    // same transducer applied to different inputs and reducers
+    assert_eq!(transduce(&xf, push, Vec::new(), vec![1,2,3,4,5]), vec![2,4,6]);
+    assert_eq!(transduce(&xf, push, Vec::new(), vec![10,11]), vec![12]);
+    assert_eq!(transduce(&xf, |acc, x| acc + x, 0, 0..10), 2+4+6+8+10);
+
+    // taking stops the reduction early, and restarts for each application
+    let first_two = mapping(|x: i32| x * 10).then(taking(2));
+    assert_eq!(transduce(&first_two, push, Vec::new(), 1..), vec![10,20]);
+    assert_eq!(transduce(&first_two, push, Vec::new(), vec![5]), vec![50]);
+    assert_eq!(transduce(&taking(0), push, Vec::new(), 1..), vec![]);
+}
+
+#[test]
+// fn chunked_fold
+fn chunked_fold() {
+    use kaguya_rs::basic_fn::fun::chunked_fold;
+    use std::cell::Cell;
+    let calls = Cell::new(0);
+    let total = chunked_fold(3, 0, |acc, xs: &[i32]| {
+        calls.set(calls.get() + 1);
+        acc + xs.iter().sum::<i32>()
+    }, 1..=7);
+    assert_eq!(total, (1..=7).sum::<i32>());
+    assert_eq!(calls.get(), 3);
+    let mut sizes = chunked_fold(3, Vec::new(), |mut acc, xs: &[i32]| { acc.push(xs.len()); acc }, 1..=7);
+    assert_eq!(sizes, vec![3,3,1]);
+    sizes = chunked_fold(3, Vec::new(), |mut acc, xs: &[i32]| { acc.push(xs.len()); acc }, vec![]);
+    assert!(sizes.is_empty());
+}
+
+#[test]
+#[should_panic]
+// fn chunked_fold
+fn chunked_fold_zero() {
+    use kaguya_rs::basic_fn::fun::chunked_fold;
+    chunked_fold(0, 0, |acc, xs: &[i32]| acc + xs.len(), vec![1]);
+}
+
+#[test]
+// fn monotonic_runs
+fn monotonic_runs() {
+    use kaguya_rs::basic_fn::fun::monotonic_runs;
+    assert_eq!(monotonic_runs(vec![1,3,2,2,5,1]), vec![vec![1,3], vec![2,2,5], vec![1]]);
+    assert_eq!(monotonic_runs(vec![3,2,1]), vec![vec![3], vec![2], vec![1]]);
+    assert_eq!(monotonic_runs(Vec::<i32>::new()), Vec::<Vec<i32>>::new());
+}
+
+#[test]
+// fn merge
+fn merge() {
+    use kaguya_rs::basic_fn::fun::{merge, merge_by};
+    assert_eq!(merge(vec![1,4,6], vec![2,3,7,8]).collect::<Vec<_>>(), vec![1,2,3,4,6,7,8]);
+    assert_eq!(merge(vec![], vec![1,2]).collect::<Vec<_>>(), vec![1,2]);
+    // ties keep elements of left side first
+    let m = merge_by(|x: &(i32, char), y| x.0.cmp(&y.0), vec![(1,'a'),(2,'a')], vec![(1,'b'),(2,'b')]);
+    assert_eq!(m.collect::<Vec<_>>(), vec![(1,'a'),(1,'b'),(2,'a'),(2,'b')]);
+}
+
+#[test]
+// fn merge_sort
+fn merge_sort() {
+    use kaguya_rs::basic_fn::fun::{merge_sort, merge_sort_by};
+    // linear congruential generator for deterministic pseudo random data
+    let mut seed = 42u64;
+    let mut next = move || { seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407); (seed >> 33) % 100 };
+    let random: Vec<u64> = (0..500).map(|_| next()).collect();
+    let mut expected = random.clone();
+    expected.sort();
+    assert_eq!(merge_sort(random.clone()), expected);
+
+    let mut nearly: Vec<u64> = (0..500).collect();
+    nearly.swap(10, 20);
+    nearly.swap(300, 301);
+    let mut expected = nearly.clone();
+    expected.sort();
+    assert_eq!(merge_sort(nearly), expected);
+
+    // stable on equal keys
+    let pairs: Vec<(u64, usize)> = random.iter().cloned().zip(0..).collect();
+    let mut expected = pairs.clone();
+    expected.sort_by_key(|p| p.0);
+    assert_eq!(merge_sort_by(|x, y| x.0.cmp(&y.0), pairs), expected);
+    assert_eq!(merge_sort(Vec::<i32>::new()), vec![]);
+}
+
+#[test]
+// fn binary_search
+fn binary_search() {
+    use kaguya_rs::basic_fn::fun::{binary_search, binary_search_by};
+    assert_eq!(binary_search(3, vec![1,2,3,5]), Ok(2));
+    assert_eq!(binary_search(4, vec![1,2,3,5]), Err(3));
+    assert_eq!(binary_search(0, &[1,2][..]), Err(0));
+    let v = vec![(1,'a'),(3,'b'),(7,'c')];
+    assert_eq!(binary_search_by(|p| p.0.cmp(&7), &v), Ok(2));
+    assert_eq!(binary_search_by(|p| p.0.cmp(&2), &v), Err(1));
+}
+
+#[test]
+// fn bisect
+fn bisect() {
+    use kaguya_rs::basic_fn::fun::{bisect_left, bisect_right};
+    assert_eq!(bisect_left(2, vec![1,2,2,3]), 1);
+    assert_eq!(bisect_right(2, vec![1,2,2,3]), 3);
+    assert_eq!(bisect_left(9, vec![1,2,2,3]), 4);
+    assert_eq!(bisect_right(0, vec![1,2,2,3]), 0);
+    assert_eq!(bisect_left(0, Vec::<i32>::new()), 0);
+}
+
+#[test]
+// fn sliding_max, sliding_min
+fn sliding_max_min() {
+    use kaguya_rs::basic_fn::fun::{sliding_max, sliding_min};
+    assert_eq!(sliding_max(3, vec![1,3,-1,-3,5,3,6,7]).collect::<Vec<_>>(), vec![3,3,5,5,6,7]);
+    assert_eq!(sliding_min(3, vec![1,3,-1,-3,5,3,6,7]).collect::<Vec<_>>(), vec![-1,-3,-3,-3,3,3]);
+    assert_eq!(sliding_max(4, vec![1,2]).count(), 0);
+
+    // compare with naive implementation on pseudo random data
+    let mut seed = 7u64;
+    let mut next = move || { seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407); (seed >> 33) % 50 };
+    let data: Vec<u64> = (0..300).map(|_| next()).collect();
+    for n in 1..10 {
+        let naive_max: Vec<u64> = data.windows(n).map(|w| *w.iter().max().unwrap()).collect();
+        let naive_min: Vec<u64> = data.windows(n).map(|w| *w.iter().min().unwrap()).collect();
+        assert_eq!(sliding_max(n, data.clone()).collect::<Vec<_>>(), naive_max);
+        assert_eq!(sliding_min(n, data.clone()).collect::<Vec<_>>(), naive_min);
+    }
+}
+
+#[test]
+#[should_panic]
+// fn sliding_max
+fn sliding_max_zero() {
+    use kaguya_rs::basic_fn::fun::sliding_max;
+    sliding_max(0, vec![1]).count();
+}
+
+#[test]
+// fn zip_reduce
+fn zip_reduce() {
+    use kaguya_rs::basic_fn::fun::zip_reduce;
+    assert_eq!(zip_reduce(0, |acc, x, y| acc + x * y, [1,2,3], [4,5,6]), 32);
+    assert_eq!(zip_reduce(0, |acc, x, y| acc + x * y, vec![1,2,3], vec![4]), 4);
+    assert_eq!(zip_reduce(String::new(), |acc, c, n| acc + &c.to_string().repeat(n), "ab".chars(), 1..), "abb");
+}
+
+#[test]
+// fn every
+fn every() {
+    use kaguya_rs::basic_fn::fun::every;
+    assert_eq!(every(3, 1, 0..=9).collect::<Vec<_>>(), vec![1,4,7]);
+    assert_eq!(every(1, 0, 0..3).collect::<Vec<_>>(), vec![0,1,2]);
+    assert_eq!(every(2, 5, 0..3).count(), 0);
+}
+
+#[test]
+#[should_panic]
+// fn every
+fn every_zero_step() {
+    use kaguya_rs::basic_fn::fun::every;
+    every(0, 1, 0..3).count();
+}
+
+#[test]
+// fn deinterleave
+fn deinterleave() {
+    use kaguya_rs::basic_fn::fun::deinterleave;
+    assert_eq!(deinterleave(2, vec![1,2,3,4,5]), vec![vec![1,3,5], vec![2,4]]);
+    assert_eq!(deinterleave(3, vec![1]), vec![vec![1], vec![], vec![]]);
+    assert_eq!(deinterleave(1, 0..3), vec![vec![0,1,2]]);
+}
+
+#[test]
+// fn rle_full, rle_full_by
+fn rle_full() {
+    use kaguya_rs::basic_fn::fun::{rle_full, rle_full_by};
+    assert_eq!(rle_full(vec!['a','a','b','a']), vec![('a', 2, vec!['a','a']), ('b', 1, vec!['b']), ('a', 1, vec!['a'])]);
+    assert_eq!(rle_full(Vec::<i32>::new()), vec![]);
+    assert_eq!(rle_full_by(|x| x / 10, vec![11,15,23,29,24,12]),
+               vec![(1, 2, vec![11,15]), (2, 3, vec![23,29,24]), (1, 1, vec![12])]);
+}
+
+#[test]
+// fn scanl1
+fn scanl1() {
+    use kaguya_rs::basic_fn::fun::scanl1;
+    assert_eq!(scanl1(|a, b| a + b, vec![1,2,3,4]).collect::<Vec<_>>(), vec![1,3,6,10]);
+    assert_eq!(scanl1(|a, b| a + b, Vec::<i32>::new()).collect::<Vec<_>>(), vec![]);
+    assert_eq!(scanl1(std::cmp::max, vec![3,1,4,1,5]).collect::<Vec<_>>(), vec![3,3,4,4,5]);
+}
+
+#[test]
+// fn fold_validate
+fn fold_validate() {
+    use kaguya_rs::basic_fn::control::fold_validate;
+    assert_eq!(fold_validate(0, |a, x| a + x, vec![Ok(1), Err("x"), Ok(2), Err("y")]), Err(vec!["x","y"]));
+    assert_eq!(fold_validate(0, |a, x| a + x, vec![Ok::<i32, &str>(1), Ok(2)]), Ok(3));
+    assert_eq!(fold_validate(0, |a, x: i32| a + x, Vec::<Result<i32, ()>>::new()), Ok(0));
+}
+
+#[test]
+// fn memoize, memoize2
+fn memoize() {
+    use kaguya_rs::basic_fn::memo::{memoize, memoize2};
+    use std::cell::Cell;
+    let calls = Cell::new(0);
+    let mut square = memoize(|x: u64| { calls.set(calls.get() + 1); x * x });
+    assert_eq!(square(3), 9);
+    assert_eq!(square(3), 9);
+    assert_eq!(square(4), 16);
+    assert_eq!(calls.get(), 2);
+
+    // count of monotonic lattice paths from (0, 0) to (i, j)
+    let calls = Cell::new(0);
+    let mut paths = memoize2(|i: u64, j: u64| {
+        calls.set(calls.get() + 1);
+        (1..=j).fold(1, |acc, k| acc * (i + k) / k)
+    });
+    for _ in 0..3 {
+        for i in 0..5 {
+            for j in 0..5 {
+                paths(i, j);
+            }
+        }
+    }
+    assert_eq!(paths(2, 2), 6);
+    assert_eq!(paths(4, 3), 35);
+    assert_eq!(calls.get(), 25);
+}
+
+#[test]
+// fn take_per_key
+fn take_per_key() {
+    use kaguya_rs::basic_fn::fun::take_per_key;
+    assert_eq!(take_per_key(2, |x| x % 2, vec![1,3,5,2,4,6]).collect::<Vec<_>>(), vec![1,3,2,4]);
+    assert_eq!(take_per_key(1, |s: &&str| s.len(), vec!["a","bb","c","dd","eee"]).collect::<Vec<_>>(), vec!["a","bb","eee"]);
+    assert_eq!(take_per_key(0, |x| *x, 0..3).count(), 0);
+    assert_eq!(take_per_key(1, |x| x % 3, 0..).take(3).collect::<Vec<_>>(), vec![0,1,2]);
+}
+
+#[test]
+#[cfg(feature = "rand")]
+// fn reservoir_sample
+fn reservoir_sample() {
+    use kaguya_rs::basic_fn::random::reservoir_sample;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    let a = reservoir_sample(5, 0..1000, &mut StdRng::seed_from_u64(42));
+    let b = reservoir_sample(5, 0..1000, &mut StdRng::seed_from_u64(42));
+    assert_eq!(a.len(), 5);
+    assert_eq!(a, b);
+    assert!(a.iter().all(|x| *x < 1000));
+    assert_ne!(a, reservoir_sample(5, 0..1000, &mut StdRng::seed_from_u64(7)));
+    // shorter input is returned whole
+    let mut rng = StdRng::seed_from_u64(1);
+    assert_eq!(reservoir_sample(5, vec![1,2], &mut rng), vec![1,2]);
+
+    // every element is roughly equally likely to be sampled
+    let mut hits = [0; 10];
+    for _ in 0..10_000 {
+        for x in reservoir_sample(3, 0..10, &mut rng) {
+            hits[x] += 1;
+        }
+    }
+    assert!(hits.iter().all(|h| (2_700..3_300).contains(h)));
+}
+
+#[test]
+#[cfg(feature = "rand")]
+// fn shuffle
+fn shuffle() {
+    use kaguya_rs::basic_fn::random::shuffle;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    let v: Vec<i32> = (0..20).collect();
+    let a = shuffle(v.iter().cloned(), &mut StdRng::seed_from_u64(42));
+    assert_eq!(a, shuffle(v.iter().cloned(), &mut StdRng::seed_from_u64(42)));
+    assert_ne!(a, v);
+    let mut sorted = a.clone();
+    sorted.sort();
+    assert_eq!(sorted, v);
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut dup = shuffle(vec!['a','b','a'], &mut rng);
+    dup.sort();
+    assert_eq!(dup, vec!['a','a','b']);
+    assert_eq!(shuffle(Vec::<i32>::new(), &mut rng), vec![]);
+}
+
+#[test]
+// fn chunks_weighted
+fn chunks_weighted() {
+    use kaguya_rs::basic_fn::fun::chunks_weighted;
+    assert_eq!(chunks_weighted(7, |x| *x, vec![3,4,2,6]), vec![vec![3,4], vec![2], vec![6]]);
+    assert_eq!(chunks_weighted(7, |x| *x, vec![9,1,1]), vec![vec![9], vec![1,1]]);
+    assert_eq!(chunks_weighted(4, |s: &&str| s.len(), vec!["ab","cd","e","fghij"]), vec![vec!["ab","cd"], vec!["e"], vec!["fghij"]]);
+    assert!(chunks_weighted(1.5, |x: &f64| *x, vec![]).is_empty());
+}
+
+#[test]
+// fn chunk_by_running_total
+fn chunk_by_running_total() {
+    use kaguya_rs::basic_fn::fun::{chunk_by_running_total, take};
+    use std::cell::Cell;
+    assert_eq!(chunk_by_running_total(7, |x| *x, vec![3,4,2,6,9]).collect::<Vec<_>>(), vec![vec![3,4], vec![2], vec![6], vec![9]]);
+
+    let pulled = Cell::new(0);
+    let chunks = chunk_by_running_total(10, |x| *x, (1..).inspect(|_| pulled.set(pulled.get() + 1)));
+    assert_eq!(take!(3)(chunks), vec![vec![1,2,3,4], vec![5], vec![6]]);
+    // only one element after the last chunk is peeked
+    assert_eq!(pulled.get(), 7);
+}
+
+#[test]
+// fn diff
+fn diff() {
+    use kaguya_rs::basic_fn::diff::diff;
+    use kaguya_rs::basic_fn::fun::lcs;
+    use kaguya_rs::basic_fn::diff::Diff::{Keep, Insert, Delete};
+    assert_eq!(diff("abc".chars(), "axc".chars()), vec![Keep('a'), Delete('b'), Insert('x'), Keep('c')]);
+    assert_eq!(diff(vec![1,2], vec![]), vec![Delete(1), Delete(2)]);
+    assert_eq!(diff(vec![], vec![1]), vec![Insert(1)]);
+
+    let (a, b) = ("kitten sitting".chars(), "sitting kitten".chars());
+    let script = diff(a.clone(), b.clone());
+    let source: String = script.iter().filter_map(|d| match d { Keep(x) | Delete(x) => Some(*x), Insert(_) => None }).collect();
+    let target: String = script.iter().filter_map(|d| match d { Keep(x) | Insert(x) => Some(*x), Delete(_) => None }).collect();
+    assert_eq!(source, a.clone().collect::<String>());
+    assert_eq!(target, b.clone().collect::<String>());
+    assert_eq!(script.iter().filter(|d| matches!(d, Keep(_))).count(), lcs(a, b).len());
+}
+
+#[test]
+// fn apply_diff
+fn apply_diff() {
+    use kaguya_rs::basic_fn::diff::{apply_diff, diff, PatchError};
+    use kaguya_rs::basic_fn::diff::Diff::{Keep, Insert, Delete};
+    let pairs = vec![("abc", "axc"), ("", "new"), ("old", ""), ("kitten sitting", "sitting kitten"), ("same", "same")];
+    for (a, b) in pairs {
+        assert_eq!(apply_diff(diff(a.chars(), b.chars()), a.chars()), Ok(b.chars().collect()));
+    }
+    assert_eq!(apply_diff(vec![Keep(1), Delete(3)], vec![1,2]), Err(PatchError { index: 1 }));
+    assert_eq!(apply_diff(vec![Keep(1), Insert(5)], vec![1,2]), Err(PatchError { index: 1 }));
+    assert_eq!(apply_diff(vec![Keep(1), Keep(2)], vec![1]), Err(PatchError { index: 1 }));
+    assert_eq!(PatchError { index: 3 }.to_string(), "diff does not match the source at index 3");
+}
+
+#[test]
+// fn zip3_longest
+fn zip3_longest() {
+    use kaguya_rs::basic_fn::fun::zip3_longest;
+    assert_eq!(zip3_longest(vec![1], vec!['a','b'], vec!["x","y","z"]).collect::<Vec<_>>(), vec![
+        (Some(1), Some('a'), Some("x")),
+        (None, Some('b'), Some("y")),
+        (None, None, Some("z")),
+    ]);
+    assert_eq!(zip3_longest(0..2, Vec::<i32>::new(), 0..1).collect::<Vec<_>>(), vec![(Some(0), None, Some(0)), (Some(1), None, None)]);
+    assert_eq!(zip3_longest(Vec::<i32>::new(), Vec::<i32>::new(), Vec::<i32>::new()).count(), 0);
+}
+
+#[test]
+// fn count_groups, count_groups_by
+fn count_groups() {
+    use kaguya_rs::basic_fn::fun::{count_groups, count_groups_by};
+    assert_eq!(count_groups(vec![1,1,2,2,2,1]), 3);
+    assert_eq!(count_groups(Vec::<i32>::new()), 0);
+    assert_eq!(count_groups("aab".chars()), 2);
+    assert_eq!(count_groups_by(|x| x / 10, vec![11,15,23,29,12]), 3);
+}
+
+#[test]
+// fn zip_try_with
+fn zip_try_with() {
+    use kaguya_rs::basic_fn::fun::zip_try_with;
+    use std::cell::Cell;
+    let parse = |k: &str, v: &str| v.parse::<i32>().map(|n| (k.to_string(), n));
+    assert_eq!(zip_try_with(parse, vec!["a","b"], vec!["1","2","3"]), Ok(vec![("a".to_string(), 1), ("b".to_string(), 2)]));
+
+    let calls = Cell::new(0);
+    let res = zip_try_with(|x: i32, y: i32| {
+        calls.set(calls.get() + 1);
+        if y == 0 { Err(format!("{} / 0", x)) } else { Ok(x / y) }
+    }, vec![4,5,6], vec![2,0,3]);
+    assert_eq!(res, Err("5 / 0".to_string()));
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+// fn feedback
+fn feedback() {
+    use kaguya_rs::basic_fn::fun::feedback;
+    // first order IIR filter: y[n] = (y[n-1] + x[n]) / 2
+    let smooth = |prev: f64, x: f64| { let y = (prev + x) / 2.0; (y, y) };
+    assert_eq!(feedback(0.0, smooth, vec![8.0, 8.0, 0.0, 4.0]).collect::<Vec<_>>(), vec![4.0, 6.0, 3.0, 3.5]);
+    // lazy on infinite inputs, output may differ from state
+    let fib = feedback((0u64, 1u64), |(a, b), _| ((b, a + b), a), 0..);
+    assert_eq!(fib.take(7).collect::<Vec<_>>(), vec![0,1,1,2,3,5,8]);
+}
+
+#[test]
+// fn filter_indexed and curry filter_indexed
+fn filter_indexed() {
+    use kaguya_rs::basic_fn::fun::filter_indexed;
+    let v = vec!['a','b','c','d'];
+    assert_eq!(filter_indexed(|i, _| i % 2 == 0, v.clone()).collect::<Vec<_>>(), vec!['a','c']);
+    assert_eq!(filter_indexed(|i, x| i > 0 && *x != 'c', v.clone()).collect::<Vec<_>>(), vec!['b','d']);
+
+    let curry = filter_indexed!(|i, _: &char| i < 2);
+    assert_eq!(curry(v).collect::<Vec<_>>(), vec!['a','b']);
+}
+
+#[test]
+// fn sample_ratio
+fn sample_ratio() {
+    use kaguya_rs::basic_fn::fun::sample_ratio;
+    assert_eq!(sample_ratio(0.5, 0..=9).collect::<Vec<_>>(), vec![1,3,5,7,9]);
+    assert_eq!(sample_ratio(0.25, 0..8).collect::<Vec<_>>(), vec![3,7]);
+    assert_eq!(sample_ratio(1.0, 0..3).collect::<Vec<_>>(), vec![0,1,2]);
+    assert_eq!(sample_ratio(0.0, 0..3).count(), 0);
+    assert_eq!(sample_ratio(0.1, 0..1000).count(), 100);
+}
+
+#[test]
+#[should_panic]
+// fn sample_ratio
+fn sample_ratio_out_of_range() {
+    use kaguya_rs::basic_fn::fun::sample_ratio;
+    sample_ratio(1.5, 0..3).count();
+}
+
+#[test]
+// macro pipe_while
+fn pipe_while() {
+    let triple_twice_then_big = pipe_while!(|x: &i32| *x < 100; |x| x * 3, |x| x * 3, |x| x + 1000);
+    // 5 -> 15 -> 45 -> 1045 exceeds, so 45 is returned
+    assert_eq!(triple_twice_then_big(5), 45);
+    // 50 -> 150 exceeds at the first stage
+    assert_eq!(triple_twice_then_big(50), 50);
+
+    let all_pass = pipe_while!(|s: &String| s.len() < 10; |s: String| s + "a", |s: String| s + "b");
+    assert_eq!(all_pass("x".to_string()), "xab");
+    assert_eq!(all_pass("123456789".to_string()), "123456789");
+}
+
+#[test]
+// fn zip_longest_with
+fn zip_longest_with() {
+    use kaguya_rs::basic_fn::fun::zip_longest_with;
+    let describe = |a: Vec<i32>, b: Vec<&str>| zip_longest_with(
+        |x, y| format!("{}{}", x, y),
+        |x| format!("{}-", x),
+        |y| format!("-{}", y),
+        a, b);
+    assert_eq!(describe(vec![1,2,3], vec!["a"]), vec!["1a", "2-", "3-"]);
+    assert_eq!(describe(vec![1], vec!["a","b"]), vec!["1a", "-b"]);
+    assert!(describe(vec![], vec![]).is_empty());
+}
+
+#[test]
+// fn memoize_with
+fn memoize_with() {
+    use kaguya_rs::basic_fn::memo::{memoize_with, Cache, LruCache};
+    use std::cell::Cell;
+    use std::collections::BTreeMap;
+
+    let calls = Cell::new(0);
+    let square = |x: u64| { calls.set(calls.get() + 1); x * x };
+    let mut tree = memoize_with(BTreeMap::new(), square);
+    assert_eq!((0..3).chain(0..3).map(&mut tree).collect::<Vec<_>>(), vec![0,1,4,0,1,4]);
+    assert_eq!(calls.get(), 3);
+
+    calls.set(0);
+    let mut lru = memoize_with(LruCache::new(2), square);
+    assert_eq!(lru(1), 1);
+    assert_eq!(lru(2), 4);
+    assert_eq!(lru(1), 1);
+    // 2 is the least recently used and evicted
+    assert_eq!(lru(3), 9);
+    assert_eq!(calls.get(), 3);
+    assert_eq!(lru(1), 1);
+    assert_eq!(calls.get(), 3);
+    assert_eq!(lru(2), 4);
+    assert_eq!(calls.get(), 4);
+
+    let mut cache = LruCache::new(1);
+    cache.insert("a", 1);
+    cache.insert("a", 2);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(&"a"), Some(&2));
+    cache.insert("b", 3);
+    assert_eq!(cache.get(&"a"), None);
+}
+
+#[test]
+// fn tee
+fn tee() {
+    use kaguya_rs::basic_fn::fun::tee;
+    use std::cell::Cell;
+    let pulled = Cell::new(0);
+    let (mut a, mut b) = tee((0..10).inspect(|_| pulled.set(pulled.get() + 1)));
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+    // consume at different rates, a leads b, then b overtakes a
+    left.extend(a.by_ref().take(3));
+    right.extend(b.by_ref().take(1));
+    left.push(a.next().unwrap());
+    right.extend(b.by_ref().take(6));
+    left.extend(a);
+    right.extend(b);
+    assert_eq!(left, (0..10).collect::<Vec<_>>());
+    assert_eq!(right, (0..10).collect::<Vec<_>>());
+    // source is pulled only once
+    assert_eq!(pulled.get(), 10);
+
+    let (all, rest) = tee(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+    assert_eq!(all.step_by(2).collect::<Vec<_>>(), vec!["a", "c"]);
+    assert_eq!(rest.skip(1).collect::<Vec<_>>(), vec!["b", "c"]);
+
+    // source resuming after None is fused, both sides stop at the first None
+    let mut n = 0;
+    let resuming = std::iter::from_fn(move || { n += 1; if n == 3 { None } else { Some(n) } });
+    let (mut a, mut b) = tee(resuming);
+    assert_eq!(a.by_ref().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(a.next(), None);
+    assert_eq!(b.by_ref().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(b.next(), None);
+
+    // nothing is buffered for a dropped side, clones held by the backlog would raise the count
+    let item = std::rc::Rc::new(0);
+    let (mut a, b) = tee(std::iter::repeat(item.clone()).take(100));
+    // held by item, the source and one element in the backlog of b
+    a.next();
+    assert_eq!(std::rc::Rc::strong_count(&item), 3);
+    drop(b);
+    // backlog of the dropped side is released and stays empty
+    assert_eq!(std::rc::Rc::strong_count(&item), 2);
+    assert_eq!(a.by_ref().take(50).count(), 50);
+    assert_eq!(std::rc::Rc::strong_count(&item), 2);
+    assert_eq!(a.count(), 49);
+    assert_eq!(std::rc::Rc::strong_count(&item), 1);
+    // the surviving side still gets its backlog
+    let (a, mut b) = tee(0..5);
+    b.next();
+    b.next();
+    drop(b);
+    assert_eq!(a.collect::<Vec<_>>(), vec![0,1,2,3,4]);
+}
+
+#[test]
+// fn memoize2_rec
+fn memoize2_rec() {
+    use kaguya_rs::basic_fn::memo::memoize2_rec;
+    use std::cell::Cell;
+    let (a, b): (Vec<char>, Vec<char>) = ("kitten".chars().collect(), "sitting".chars().collect());
+    let calls = Cell::new(0);
+    // edit distance of a[i..] and b[j..]
+    let mut dist = memoize2_rec(|dist, i: usize, j: usize| -> usize {
+        calls.set(calls.get() + 1);
+        if i == a.len() {
+            b.len() - j
+        } else if j == b.len() {
+            a.len() - i
+        } else if a[i] == b[j] {
+            dist(i + 1, j + 1)
+        } else {
+            1 + dist(i + 1, j).min(dist(i, j + 1)).min(dist(i + 1, j + 1))
+        }
+    });
+    assert_eq!(dist(0, 0), 3);
+    assert_eq!(calls.get(), (a.len() + 1) * (b.len() + 1));
+    assert_eq!(dist(0, 0), 3);
+    assert_eq!(dist(6, 7), 0);
+    assert_eq!(calls.get(), (a.len() + 1) * (b.len() + 1));
 }
\ No newline at end of file
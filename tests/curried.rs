@@ -0,0 +1,52 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+extern crate kaguya_rs;
+
+use kaguya_rs::curried;
+
+#[curried]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[curried]
+fn neg(a: i32) -> i32 {
+    -a
+}
+
+#[curried]
+pub fn join(sep: String, v: Vec<String>, last: &'static str, n: usize) -> String {
+    let mut ret = v.join(&sep);
+    for _ in 0..n {
+        ret.push_str(&sep);
+        ret.push_str(last);
+    }
+    ret
+}
+
+#[curried]
+fn pair(a: std::string::String, b: Option<(u8, Vec<u8>)>) -> (String, Option<(u8, Vec<u8>)>) {
+    (a, b)
+}
+
+#[test]
+// attribute curried
+fn curried() {
+    assert_eq!(add_curried(1)(2), 3);
+    assert_eq!(add(1, 2), 3);
+    assert_eq!(neg_curried(1), -1);
+
+    let add_one = add_curried(1);
+    assert_eq!((0..3).map(&add_one).collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    let f = join_curried(", ".to_string())(vec!["a".to_string(), "b".to_string()])("c");
+    assert_eq!(f(1), "a, b, c");
+    assert_eq!(f(2), "a, b, c, c");
+
+    assert_eq!(pair_curried("a".to_string())(None), ("a".to_string(), None));
+}
@@ -0,0 +1,139 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+extern crate kaguya_rs;
+
+#[test]
+// data Reader
+fn reader() {
+    use kaguya_rs::data::reader::{Reader, with_env};
+    struct Config { name: String, level: u32 }
+    let config = Config { name: "Kaguya".to_string(), level: 3 };
+
+    let greeting = Reader::new(|c: &Config| format!("hello {}", c.name));
+    let level = Reader::new(|c: &Config| c.level);
+    let both = greeting.zip(level).map(|(g, l)| format!("{} at {}", g, l));
+    assert_eq!(both.run(&config), "hello Kaguya at 3");
+
+    let repeated = Reader::new(|c: &Config| c.level)
+        .and_then(|n| Reader::new(move |c: &Config| c.name.repeat(n as usize)));
+    assert_eq!(repeated.run(&config), "KaguyaKaguyaKaguya");
+
+    assert_eq!(Reader::ask().map(|n: i32| n * 2).run(&21), 42);
+    assert_eq!(Reader::pure(1).run(&"ignored"), 1);
+    assert_eq!(with_env(config, |c| repeated.run(c).len()), 18);
+}
+
+#[test]
+// data Writer
+fn writer() {
+    use kaguya_rs::data::writer::{Writer, tell};
+    use kaguya_rs::typeclass::monoid::Sum;
+    let double = |x: i32| tell(vec![format!("double {}", x)]).map(move |_| x * 2);
+    let inc = |x: i32| tell(vec![format!("inc {}", x)]).map(move |_| x + 1);
+    let (value, log) = Writer::pure(5).and_then(double).and_then(inc).run();
+    assert_eq!(value, 11);
+    assert_eq!(log, vec!["double 5".to_string(), "inc 10".to_string()]);
+
+    let counted = Writer::new("a", Sum(1)).and_then(|s| Writer::new(s.len(), Sum(2)));
+    assert_eq!(*counted.value(), 1);
+    assert_eq!(*counted.log(), Sum(3));
+}
+
+#[test]
+// data State
+fn state() {
+    use kaguya_rs::data::state::{State, get, put, modify, run_state};
+    let counter = get()
+        .and_then(|n: i32| modify(move |s| s + n * 10).map(move |_| n))
+        .and_then(|old| get().map(move |new| (old, new)));
+    assert_eq!(run_state(1, counter), ((1, 11), 11));
+
+    let reset = modify(|s: Vec<i32>| { let mut s = s; s.push(3); s }).and_then(|_| put(vec![0]));
+    assert_eq!(reset.run(vec![1, 2]), ((), vec![0]));
+    assert_eq!(State::pure('k').run("unchanged"), ('k', "unchanged"));
+}
+
+#[test]
+// data List
+fn list() {
+    use kaguya_rs::data::list::List;
+    let l: List<i32> = (1..=4).collect();
+    assert_eq!(l.head(), Some(&1));
+    assert_eq!(l.len(), 4);
+    let t = l.tail().unwrap();
+    assert!(t.ptr_eq(&l.tail().unwrap()));
+    let l2 = t.cons(9);
+    assert!(l2.tail().unwrap().ptr_eq(&t));
+    assert_eq!(l2.iter().cloned().collect::<Vec<_>>(), vec![9,2,3,4]);
+    // original list is untouched
+    assert_eq!(l, (1..=4).collect());
+
+    assert_eq!(l.map(|x| x * 10), vec![10,20,30,40].into_iter().collect());
+    assert_eq!(l.filter(|x| x % 2 == 0), List::new().cons(4).cons(2));
+    assert_eq!(l.foldr(String::new(), |x, acc| acc + &x.to_string()), "4321");
+    assert_eq!(l2.into_iter().collect::<Vec<_>>(), vec![9,2,3,4]);
+    assert_eq!(format!("{:?}", t), "[2, 3, 4]");
+    assert!(List::<i32>::new().tail().is_none());
+
+    // long list can be dropped without overflowing stack
+    let long: List<u32> = (0..1_000_000).collect();
+    assert_eq!(long.head(), Some(&0));
+}
+
+#[test]
+// data PVec
+fn pvec() {
+    use kaguya_rs::data::pvec::PVec;
+    let v: PVec<usize> = (0..10_000).collect();
+    assert_eq!(v.len(), 10_000);
+    assert!((0..10_000).all(|i| v.get(i) == Some(&i)));
+    assert_eq!(v.get(10_000), None);
+
+    let w = v.update(5_000, 0);
+    assert_eq!(w.get(5_000), Some(&0));
+    assert_eq!(v.get(5_000), Some(&5_000));
+    assert!(v.iter().zip(w.iter()).enumerate().all(|(i, (a, b))| i == 5_000 || a == b));
+    assert!(v.iter().cloned().eq(0..10_000));
+
+    let e = PVec::new().push('a').push('b');
+    assert_eq!(e.iter().collect::<String>(), "ab");
+    assert!(PVec::<i32>::new().is_empty());
+}
+
+#[test]
+#[should_panic]
+// data PVec
+fn pvec_update_out_of_bounds() {
+    use kaguya_rs::data::pvec::PVec;
+    PVec::new().push(1).update(1, 2);
+}
+
+#[test]
+// data Zipper
+fn zipper() {
+    use kaguya_rs::data::list::List;
+    let l: List<i32> = (1..=5).collect();
+    let z = l.zipper();
+    assert_eq!(z.focus(), Some(&1));
+    assert!(z.left().is_none());
+
+    let mid = z.right().and_then(|z| z.right()).unwrap();
+    assert_eq!(mid.focus(), Some(&3));
+    let replaced = mid.set(30).unwrap();
+    assert_eq!(replaced.to_list(), vec![1,2,30,4,5].into_iter().collect());
+    assert_eq!(replaced.left().unwrap().focus(), Some(&2));
+    assert_eq!(mid.insert(0).to_list(), vec![1,2,0,3,4,5].into_iter().collect());
+    assert_eq!(mid.delete().unwrap().to_list(), vec![1,2,4,5].into_iter().collect());
+    // original list and zipper are untouched
+    assert_eq!(mid.to_list(), l);
+
+    let end = (0..5).fold(z, |z, _| z.right().unwrap());
+    assert_eq!(end.focus(), None);
+    assert!(end.right().is_none() && end.set(0).is_none() && end.delete().is_none());
+    assert_eq!(end.insert(6).to_list(), (1..=6).collect());
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Kept in its own test binary as the counting allocator is process wide.
+
+extern crate kaguya_rs;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[test]
+// fn transduce builds no intermediate collection
+fn transduce_no_alloc() {
+    use kaguya_rs::basic_fn::transducer::{transduce, mapping, filtering, taking, Transducer};
+    let xf = mapping(|x: u64| x * 3).then(filtering(|x: &u64| x % 2 == 0)).then(taking(1_000));
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    let total = transduce(&xf, |acc, x| acc + x, 0, 0..1_000_000);
+    let after = ALLOCATIONS.load(Ordering::SeqCst);
+    assert_eq!(total, (0..1_000u64).map(|x| x * 6).sum::<u64>());
+    assert_eq!(after - before, 0);
+
+    // the counter does see allocations of building a Vec
+    let pushed = transduce(&xf, |mut v: Vec<u64>, x| { v.push(x); v }, Vec::new(), 0..10);
+    assert_eq!(pushed, vec![0, 6, 12, 18, 24]);
+    assert!(ALLOCATIONS.load(Ordering::SeqCst) > after);
+}
@@ -0,0 +1,20 @@
+// Copyright 2018 KaguyaRs Developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+extern crate kaguya_rs;
+
+#[test]
+// typeclass Monoid
+fn monoid() {
+    use kaguya_rs::typeclass::monoid::{Monoid, Sum, Product};
+    assert_eq!(Sum::empty(), Sum(0));
+    assert_eq!(Sum(1.5).combine(Sum(2.0)), Sum(3.5));
+    assert_eq!(Product::empty(), Product(1_u64));
+    assert_eq!(Product(2).combine(Product(3)), Product(6));
+    assert_eq!(String::empty().combine("Kaguya".to_string()), "Kaguya");
+    assert_eq!(vec![1].combine(Vec::empty()).combine(vec![2]), vec![1, 2]);
+}